@@ -0,0 +1,192 @@
+use std::ops::{Add, Index, Sub};
+
+use crate::{search, search::Search, Directed, Graph, Kind, NegativeCycle, Node, Path, WeakNode};
+
+impl<T, E, K: Kind> Graph<T, E, K> {
+    /// Find the shortest paths between every pair of nodes with the Floyd-Warshall algorithm.
+    ///
+    /// This takes O(V³) time regardless of the number of edges, so it suits dense graphs. Negative edge weights are
+    /// allowed.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `NegativeCycle` if the graph contains a cycle whose total weight is negative.
+    pub fn floyd_warshall(&self) -> Result<DistanceMatrix<'_, T, E, K>, NegativeCycle<'_, T, E, K>>
+    where
+        E: Default + Clone + Ord + Add<E, Output = E>,
+    {
+        let nodes: Vec<usize> = self.indices().collect();
+        let mut rows: Vec<Search<E>> = (0..self.nodes.len())
+            .map(|_| Search {
+                distance: vec![None; self.nodes.len()],
+                predecessor: vec![None; self.nodes.len()],
+                settled: Vec::new(),
+            })
+            .collect();
+        for &node in &nodes {
+            let row = &mut rows[node];
+            row.distance[node] = Some(E::default());
+            for (next, edge, weight) in self.weighted_edges(node) {
+                if row.distance[next]
+                    .as_ref()
+                    .is_some_and(|old| old <= &weight)
+                {
+                    continue;
+                }
+                row.distance[next] = Some(weight);
+                row.predecessor[next] = Some((node, edge));
+            }
+        }
+
+        for &via in &nodes {
+            let from_via = rows[via].distance.clone();
+            let via_predecessor = rows[via].predecessor.clone();
+            for &start in &nodes {
+                let row = &mut rows[start];
+                let Some(to_via) = row.distance[via].clone() else {
+                    continue;
+                };
+                for &end in &nodes {
+                    let Some(from_via) = from_via[end].clone() else {
+                        continue;
+                    };
+                    let new_dist = to_via.clone() + from_via;
+                    if row.distance[end]
+                        .as_ref()
+                        .is_some_and(|old| old <= &new_dist)
+                    {
+                        continue;
+                    }
+                    row.distance[end] = Some(new_dist);
+                    row.predecessor[end] = via_predecessor[end];
+                }
+            }
+            // stop as soon as a node can reach itself at a negative cost, before the distances diverge
+            if let Some(&start) = nodes
+                .iter()
+                .find(|&&node| rows[node].distance[node].as_ref() < Some(&E::default()))
+            {
+                self.bellman_ford(Node {
+                    graph: self,
+                    idx: start,
+                })?;
+            }
+        }
+        Ok(DistanceMatrix { graph: self, rows })
+    }
+
+    /// Find the shortest paths between every pair of nodes with Johnson's algorithm.
+    ///
+    /// The edges are reweighted using potentials from Bellman-Ford so that Dijkstra's algorithm can be run from
+    /// every node, taking O(V * E log V) time. This suits sparse graphs with negative edge weights; reweighting is why
+    /// the weights also need to be subtracted.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `NegativeCycle` if the graph contains a cycle whose total weight is negative.
+    pub fn johnson(&self) -> Result<DistanceMatrix<'_, T, E, K>, NegativeCycle<'_, T, E, K>>
+    where
+        E: Default + Clone + Ord + Add<E, Output = E> + Sub<E, Output = E>,
+    {
+        let nodes: Vec<usize> = self.indices().collect();
+        // starting every node at zero is the same as adding a source with an edge of weight zero to each of them
+        let mut potential = vec![None; self.nodes.len()];
+        for &node in &nodes {
+            potential[node] = Some(E::default());
+        }
+        let mut predecessor = vec![None; self.nodes.len()];
+        if let Some((path, edges)) =
+            search::bellman_ford(&nodes, &mut potential, &mut predecessor, |node| {
+                self.weighted_edges(node)
+            })
+        {
+            return Err(NegativeCycle::new(Path {
+                graph: self,
+                nodes: path,
+                edges,
+            }));
+        }
+        let potential: Vec<E> = potential
+            .into_iter()
+            .map(Option::unwrap_or_default)
+            .collect();
+
+        let mut rows: Vec<Search<E>> = (0..self.nodes.len())
+            .map(|_| Search {
+                distance: Vec::new(),
+                predecessor: Vec::new(),
+                settled: Vec::new(),
+            })
+            .collect();
+        for &start in &nodes {
+            // the reweighted edges are never negative, and every path between two nodes changes by the same amount
+            let mut search = search::dijkstra(self.nodes.len(), start, None, |node| {
+                let potential = &potential;
+                self.weighted_edges(node).map(move |(next, edge, weight)| {
+                    (
+                        next,
+                        edge,
+                        weight + potential[node].clone() - potential[next].clone(),
+                    )
+                })
+            });
+            for (end, distance) in search.distance.iter_mut().enumerate() {
+                if let Some(distance) = distance {
+                    *distance =
+                        distance.clone() + potential[end].clone() - potential[start].clone();
+                }
+            }
+            rows[start] = search;
+        }
+        Ok(DistanceMatrix { graph: self, rows })
+    }
+}
+
+/// The shortest distances between every pair of nodes in a `Graph`
+///
+/// Indexing with a pair of `WeakNode`s gives the distance from the first to the second, or `None` if there is no
+/// path between them.
+pub struct DistanceMatrix<'a, T, E, K = Directed> {
+    graph: &'a Graph<T, E, K>,
+    /// The shortest path tree from each slot
+    rows: Vec<Search<E>>,
+}
+
+impl<'a, T, E, K: Kind> DistanceMatrix<'a, T, E, K> {
+    /// Returns the length of the shortest path from `start` to `end`, or `None` if there is no path or either node is
+    /// not part of the graph.
+    #[must_use]
+    pub fn distance(&self, start: WeakNode<T, E>, end: WeakNode<T, E>) -> Option<&E> {
+        let start = self.graph.resolve(start).ok()?;
+        let end = self.graph.resolve(end).ok()?;
+        self.rows[start].distance[end].as_ref()
+    }
+
+    /// Returns the shortest path from `start` to `end`, or `None` if there is no path or either node is not part of
+    /// the graph.
+    #[must_use]
+    pub fn path(&self, start: WeakNode<T, E>, end: WeakNode<T, E>) -> Option<Path<'a, T, E, K>> {
+        let start = self.graph.resolve(start).ok()?;
+        let end = self.graph.resolve(end).ok()?;
+        self.rows[start].path(self.graph, start, end)
+    }
+}
+
+impl<T, E, K: Kind> Index<(WeakNode<T, E>, WeakNode<T, E>)> for DistanceMatrix<'_, T, E, K> {
+    type Output = Option<E>;
+
+    /// # Panics
+    ///
+    /// Panics if either node is not a live node of the graph.
+    fn index(&self, (start, end): (WeakNode<T, E>, WeakNode<T, E>)) -> &Self::Output {
+        let start = self
+            .graph
+            .resolve(start)
+            .unwrap_or_else(|err| panic!("Attempt to index distances with an invalid node: {err}"));
+        let end = self
+            .graph
+            .resolve(end)
+            .unwrap_or_else(|err| panic!("Attempt to index distances with an invalid node: {err}"));
+        &self.rows[start].distance[end]
+    }
+}
@@ -0,0 +1,86 @@
+use std::ops::Add;
+
+use crate::{search, Directed, Graph, Kind, Node, Path};
+
+impl<T, E, K: Kind> Graph<T, E, K> {
+    /// Returns the shortest path between two nodes, using `heuristic` to estimate the remaining distance from
+    /// each node to `end`.
+    ///
+    /// The path is optimal as long as the heuristic never overestimates. See `astar_search` to also find out how
+    /// many nodes were expanded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either the start or end node is not part of this graph.
+    #[must_use]
+    pub fn astar(
+        &self,
+        start: Node<'_, T, E, K>,
+        end: Node<'_, T, E, K>,
+        heuristic: impl Fn(Node<'_, T, E, K>) -> E,
+    ) -> Option<Path<'_, T, E, K>>
+    where
+        E: Default + Clone + Ord + Add<E, Output = E>,
+    {
+        self.astar_search(start, end, heuristic).path
+    }
+
+    /// Runs an A* search between two nodes, reporting the path along with how many nodes were expanded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either the start or end node is not part of this graph.
+    #[must_use]
+    pub fn astar_search(
+        &self,
+        start: Node<'_, T, E, K>,
+        end: Node<'_, T, E, K>,
+        heuristic: impl Fn(Node<'_, T, E, K>) -> E,
+    ) -> AStar<'_, T, E, K>
+    where
+        E: Default + Clone + Ord + Add<E, Output = E>,
+    {
+        for node in [start, end] {
+            self.check(node).unwrap_or_else(|err| {
+                panic!("Attempt to generate path for node outside of graph: {err}")
+            });
+        }
+        let search = search::astar(
+            self.nodes.len(),
+            start.idx,
+            end.idx,
+            |node| self.weighted_edges(node),
+            |idx| heuristic(Node { graph: self, idx }),
+        );
+        AStar {
+            path: search.path(self, start.idx, end.idx),
+            expanded: search.settled.len(),
+        }
+    }
+}
+
+/// The outcome of an A* search
+pub struct AStar<'a, T, E, K = Directed> {
+    path: Option<Path<'a, T, E, K>>,
+    expanded: usize,
+}
+
+impl<'a, T, E, K: Kind> AStar<'a, T, E, K> {
+    /// Returns the shortest path that was found, if the end node is reachable
+    #[must_use]
+    pub const fn path(&self) -> Option<&Path<'a, T, E, K>> {
+        self.path.as_ref()
+    }
+
+    /// Returns the shortest path that was found, if the end node is reachable
+    #[must_use]
+    pub fn into_path(self) -> Option<Path<'a, T, E, K>> {
+        self.path
+    }
+
+    /// Returns how many times a node was taken off the queue and had its edges explored
+    #[must_use]
+    pub const fn expanded(&self) -> usize {
+        self.expanded
+    }
+}
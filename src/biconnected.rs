@@ -0,0 +1,149 @@
+use std::collections::BTreeSet;
+
+use crate::{Graph, Kind, WeakEdge, WeakNode};
+
+impl<T, E, K: Kind> Graph<T, E, K> {
+    /// Find the nodes whose removal would split their connected component, ignoring the direction of edges.
+    ///
+    /// Nodes are listed in the order of their slots, which can differ from insertion order once `remove_node` has
+    /// freed a slot for reuse.
+    #[must_use]
+    pub fn articulation_points(&self) -> Vec<WeakNode<T, E>> {
+        let blocks = self.blocks();
+        self.indices()
+            .filter(|&idx| blocks.articulation[idx])
+            .map(|idx| self.handle(idx))
+            .collect()
+    }
+
+    /// Find the edges whose removal would split their connected component, ignoring the direction of edges.
+    ///
+    /// Each bridge is returned once, with the node in the lower slot at the front, in slot order of those first
+    /// nodes. Two nodes joined once in each direction count as joined by a single edge, while parallel edges in the
+    /// same direction each count, so they are never bridges.
+    #[must_use]
+    pub fn bridges(&self) -> Vec<WeakEdge<T, E>> {
+        let mut bridges = self.blocks().bridges;
+        bridges.sort_unstable();
+        bridges
+            .into_iter()
+            .map(|(a, b)| (self.handle(a), self.handle(b)))
+            .collect()
+    }
+
+    /// Find the biconnected components of the graph, ignoring the direction of edges.
+    ///
+    /// Each component is a maximal set of nodes that stays connected after removing any one of them, and holds
+    /// its nodes in slot order. Every edge belongs to exactly one component, so articulation points appear in
+    /// several components, a bridge forms a component of two nodes, and nodes without edges appear in none.
+    #[must_use]
+    pub fn biconnected_components(&self) -> Vec<Vec<WeakNode<T, E>>> {
+        self.blocks()
+            .components
+            .into_iter()
+            .map(|component| component.into_iter().map(|idx| self.handle(idx)).collect())
+            .collect()
+    }
+
+    /// Run an iterative depth first search that tracks the discovery time of each node and the lowest discovery
+    /// time reachable from its subtree through a single back edge, splitting the edges into components as it goes
+    fn blocks(&self) -> Blocks {
+        let neighbors = self.undirected_neighbors();
+        let mut blocks = Blocks {
+            articulation: vec![false; self.nodes.len()],
+            bridges: Vec::new(),
+            components: Vec::new(),
+        };
+        let mut discovery: Vec<Option<usize>> = vec![None; self.nodes.len()];
+        let mut low = vec![0; self.nodes.len()];
+        let mut next_neighbor = vec![0; self.nodes.len()];
+        let mut edges: Vec<(usize, usize)> = Vec::new();
+        let mut time = 0;
+
+        for root in self.indices() {
+            if discovery[root].is_some() {
+                continue;
+            }
+            discovery[root] = Some(time);
+            low[root] = time;
+            time += 1;
+            let mut root_children = 0;
+            // each node on the stack keeps its parent, and whether the tree edge from it has been passed over yet
+            let mut stack: Vec<(usize, Option<usize>, bool)> = vec![(root, None, false)];
+            while let Some((node, parent, passed)) = stack.last_mut() {
+                let (node, parent) = (*node, *parent);
+                let node_discovery = discovery[node].unwrap_or_default();
+                if let Some(&next) = neighbors[node].get(next_neighbor[node]) {
+                    next_neighbor[node] += 1;
+                    // only the tree edge itself leads back to the parent, while a parallel edge is a back edge
+                    if Some(next) == parent && !*passed {
+                        *passed = true;
+                        continue;
+                    }
+                    match discovery[next] {
+                        None => {
+                            discovery[next] = Some(time);
+                            low[next] = time;
+                            time += 1;
+                            if node == root {
+                                root_children += 1;
+                            }
+                            edges.push((node, next));
+                            stack.push((next, Some(node), false));
+                        }
+                        // a back edge to an ancestor, which is seen again from the ancestor's side later
+                        Some(next_discovery) if next_discovery < node_discovery => {
+                            low[node] = low[node].min(next_discovery);
+                            edges.push((node, next));
+                        }
+                        Some(_) => {}
+                    }
+                    continue;
+                }
+
+                stack.pop();
+                let Some(parent) = parent else {
+                    continue;
+                };
+                low[parent] = low[parent].min(low[node]);
+                let parent_discovery = discovery[parent].unwrap_or_default();
+                if low[node] > parent_discovery {
+                    blocks.bridges.push((parent.min(node), parent.max(node)));
+                }
+                if low[node] >= parent_discovery {
+                    // nothing below the tree edge reaches above the parent, so its edges form a component
+                    if parent != root {
+                        blocks.articulation[parent] = true;
+                    }
+                    blocks.split_component(&mut edges, (parent, node));
+                }
+            }
+            if root_children > 1 {
+                blocks.articulation[root] = true;
+            }
+        }
+        blocks
+    }
+}
+
+/// The articulation points, bridges and biconnected components found by `Graph::blocks`, by slot index
+struct Blocks {
+    articulation: Vec<bool>,
+    bridges: Vec<(usize, usize)>,
+    components: Vec<Vec<usize>>,
+}
+
+impl Blocks {
+    /// Pop the edges down to and including the tree edge `edge` into a new component
+    fn split_component(&mut self, edges: &mut Vec<(usize, usize)>, edge: (usize, usize)) {
+        let mut component = BTreeSet::new();
+        while let Some((a, b)) = edges.pop() {
+            component.insert(a);
+            component.insert(b);
+            if (a, b) == edge {
+                break;
+            }
+        }
+        self.components.push(component.into_iter().collect());
+    }
+}
@@ -0,0 +1,176 @@
+use std::collections::VecDeque;
+
+use crate::{Graph, Kind, WeakEdge};
+
+impl<T, E, K: Kind> Graph<T, E, K> {
+    /// Find a maximum matching with Edmonds' blossom algorithm in O(V³) time, ignoring the direction of edges and
+    /// any self loops.
+    ///
+    /// Unlike `maximum_bipartite_matching` this works on any graph. Each pair is returned once, with the node in the
+    /// lower slot at the front, in slot order of those first nodes. Slots freed by `remove_node` are reused, so slot
+    /// order can differ from insertion order.
+    #[must_use]
+    pub fn maximum_matching(&self) -> Vec<WeakEdge<T, E>> {
+        let mut search = Blossom::new(self.undirected_neighbors());
+
+        // a greedy matching leaves far fewer nodes for the searches below
+        for node in self.indices() {
+            if search.mate[node].is_none() {
+                let free = search.neighbors[node]
+                    .iter()
+                    .copied()
+                    .find(|&next| search.mate[next].is_none());
+                if let Some(next) = free {
+                    search.mate[node] = Some(next);
+                    search.mate[next] = Some(node);
+                }
+            }
+        }
+        for root in self.indices() {
+            if search.mate[root].is_none() {
+                if let Some(end) = search.augmenting_path(root) {
+                    search.augment(end);
+                }
+            }
+        }
+
+        self.indices()
+            .filter_map(|node| {
+                let mate = search.mate[node].filter(|&mate| node < mate)?;
+                Some((self.handle(node), self.handle(mate)))
+            })
+            .collect()
+    }
+}
+
+/// The state of the blossom algorithm, with blossoms contracted implicitly by pointing every node inside one at
+/// its base
+struct Blossom {
+    neighbors: Vec<Vec<usize>>,
+    mate: Vec<Option<usize>>,
+    /// The node each odd node of the alternating tree was reached from, or for even nodes inside a blossom the
+    /// node across the edge that closed it
+    parent: Vec<Option<usize>>,
+    base: Vec<usize>,
+    /// Whether each node is an even node of the alternating tree, including nodes absorbed into a blossom
+    even: Vec<bool>,
+}
+
+impl Blossom {
+    fn new(neighbors: Vec<Vec<usize>>) -> Self {
+        let len = neighbors.len();
+        Self {
+            neighbors,
+            mate: vec![None; len],
+            parent: vec![None; len],
+            base: (0..len).collect(),
+            even: vec![false; len],
+        }
+    }
+
+    /// Grow an alternating tree from the unmatched `root` until it reaches another unmatched node, which is
+    /// returned as the end of an augmenting path
+    fn augmenting_path(&mut self, root: usize) -> Option<usize> {
+        let len = self.neighbors.len();
+        self.parent = vec![None; len];
+        self.base = (0..len).collect();
+        self.even = vec![false; len];
+        self.even[root] = true;
+        let mut queue = VecDeque::from([root]);
+        while let Some(node) = queue.pop_front() {
+            for i in 0..self.neighbors[node].len() {
+                let next = self.neighbors[node][i];
+                if self.base[node] == self.base[next] || self.mate[node] == Some(next) {
+                    continue;
+                }
+                let odd_cycle =
+                    next == root || self.mate[next].is_some_and(|mate| self.parent[mate].is_some());
+                if odd_cycle {
+                    // both ends are even, so the edge closes a blossom that we contract into its base
+                    self.contract(node, next, &mut queue);
+                } else if self.parent[next].is_none() {
+                    self.parent[next] = Some(node);
+                    let Some(mate) = self.mate[next] else {
+                        return Some(next);
+                    };
+                    self.even[mate] = true;
+                    queue.push_back(mate);
+                }
+            }
+        }
+        None
+    }
+
+    /// Contract the blossom closed by an edge between the even nodes `a` and `b`, queueing the odd nodes inside
+    /// it since they become even
+    fn contract(&mut self, a: usize, b: usize, queue: &mut VecDeque<usize>) {
+        let base = self.common_base(a, b);
+        let mut inside = vec![false; self.neighbors.len()];
+        self.mark_path(a, base, b, &mut inside);
+        self.mark_path(b, base, a, &mut inside);
+        for node in 0..self.neighbors.len() {
+            if inside[self.base[node]] {
+                self.base[node] = base;
+                if !self.even[node] {
+                    self.even[node] = true;
+                    queue.push_back(node);
+                }
+            }
+        }
+    }
+
+    /// The base of the lowest blossom containing both `a` and `b` in the alternating tree
+    fn common_base(&self, mut a: usize, mut b: usize) -> usize {
+        let mut seen = vec![false; self.neighbors.len()];
+        loop {
+            a = self.base[a];
+            seen[a] = true;
+            match self.mate[a].and_then(|mate| self.parent[mate]) {
+                Some(above) => a = above,
+                None => break,
+            }
+        }
+        loop {
+            b = self.base[b];
+            if seen[b] {
+                return b;
+            }
+            // every node below the root is matched and its mate was reached from above
+            match self.mate[b].and_then(|mate| self.parent[mate]) {
+                Some(above) => b = above,
+                None => return b,
+            }
+        }
+    }
+
+    /// Walk from `node` up to the blossom's `base`, marking the blossoms passed and pointing the even nodes back
+    /// along the other side of the cycle so an augmenting path can go around it
+    fn mark_path(&mut self, mut node: usize, base: usize, mut child: usize, inside: &mut [bool]) {
+        while self.base[node] != base {
+            let Some(mate) = self.mate[node] else {
+                break;
+            };
+            inside[self.base[node]] = true;
+            inside[self.base[mate]] = true;
+            self.parent[node] = Some(child);
+            child = mate;
+            match self.parent[mate] {
+                Some(above) => node = above,
+                None => break,
+            }
+        }
+    }
+
+    /// Flip the matching along the augmenting path that ends at the unmatched `end`
+    fn augment(&mut self, end: usize) {
+        let mut node = Some(end);
+        while let Some(current) = node {
+            let Some(prev) = self.parent[current] else {
+                break;
+            };
+            node = self.mate[prev];
+            self.mate[current] = Some(prev);
+            self.mate[prev] = Some(current);
+        }
+    }
+}
@@ -0,0 +1,244 @@
+use std::{collections::BTreeSet, marker::PhantomData, sync::atomic};
+
+use crate::{union_find::UnionFind, Graph, Kind, WeakNode, NEXT_GENERATION};
+
+impl<T, E, K: Kind> Graph<T, E, K> {
+    /// Find the strongly connected components of the graph with Tarjan's algorithm.
+    ///
+    /// Every node belongs to exactly one component, and each component can reach the ones before it in the list,
+    /// so the components are in reverse topological order.
+    #[must_use]
+    pub fn strongly_connected_components(&self) -> Vec<Vec<WeakNode<T, E>>> {
+        self.strong_components()
+            .into_iter()
+            .map(|component| component.into_iter().map(|idx| self.handle(idx)).collect())
+            .collect()
+    }
+
+    /// Build the graph of strongly connected components, which is always acyclic.
+    ///
+    /// Node `i` of the condensation holds the members of component `i` from `strongly_connected_components`, and
+    /// there is an edge between two components whenever there is an edge between their members. If several edges
+    /// connect the same pair of components, the weight of the first one found is kept.
+    #[must_use]
+    pub fn condensation(&self) -> Graph<Vec<WeakNode<T, E>>, E>
+    where
+        E: Clone,
+    {
+        let components = self.strong_components();
+        let mut component_of = vec![0; self.nodes.len()];
+        for (component, members) in components.iter().enumerate() {
+            for &idx in members {
+                component_of[idx] = component;
+            }
+        }
+
+        let mut condensed: Graph<Vec<WeakNode<T, E>>, E> = Graph::new();
+        let handles: Vec<_> = components
+            .iter()
+            .map(|members| {
+                condensed
+                    .insert(members.iter().map(|&idx| self.handle(idx)).collect())
+                    .weak()
+            })
+            .collect();
+        let mut connected = BTreeSet::new();
+        for idx in self.indices() {
+            for (next, _, weight) in self.edges_from(idx) {
+                let (start, end) = (component_of[idx], component_of[next]);
+                if start != end && connected.insert((start, end)) {
+                    condensed.connect_weighted(handles[start], handles[end], weight.clone());
+                }
+            }
+        }
+        condensed
+    }
+
+    /// Find the connected components of the graph when the direction of its edges is ignored.
+    ///
+    /// Components are listed in order of their first node, as are the nodes within each component.
+    #[must_use]
+    pub fn weakly_connected_components(&self) -> Vec<Vec<WeakNode<T, E>>> {
+        let mut sets = self.union_find();
+        let mut component_of = vec![None; self.nodes.len()];
+        let mut components: Vec<Vec<WeakNode<T, E>>> = Vec::new();
+        for idx in self.indices() {
+            let root = sets.find(idx);
+            let component = *component_of[root].get_or_insert_with(|| {
+                components.push(Vec::new());
+                components.len() - 1
+            });
+            components[component].push(self.handle(idx));
+        }
+        components
+    }
+
+    /// Build a `Connectivity` that answers whether two nodes are in the same weakly connected component.
+    #[must_use]
+    pub fn connectivity(&self) -> Connectivity<T, E> {
+        Connectivity {
+            graph: self.id,
+            since: NEXT_GENERATION.load(atomic::Ordering::Relaxed),
+            slots: self
+                .nodes
+                .iter()
+                .enumerate()
+                .map(|(idx, slot)| slot.adjacency.as_ref().map(|_| (slot.generation, idx)))
+                .collect(),
+            sets: self.union_find(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Union every pair of nodes joined by an edge in either direction
+    fn union_find(&self) -> UnionFind {
+        let mut sets = UnionFind::new(self.nodes.len());
+        for idx in self.indices() {
+            for next in self.successors(idx) {
+                sets.union(idx, next);
+            }
+        }
+        sets
+    }
+
+    /// Tarjan's algorithm, iterating with an explicit stack so deep graphs can't overflow the call stack
+    fn strong_components(&self) -> Vec<Vec<usize>> {
+        let mut index = vec![None; self.nodes.len()];
+        let mut low_link = vec![0; self.nodes.len()];
+        let mut on_stack = vec![false; self.nodes.len()];
+        let mut stack = Vec::new();
+        let mut components = Vec::new();
+        let mut counter = 0;
+
+        for root in self.indices() {
+            if index[root].is_some() {
+                continue;
+            }
+            index[root] = Some(counter);
+            low_link[root] = counter;
+            counter += 1;
+            stack.push(root);
+            on_stack[root] = true;
+            let mut calls = vec![(root, self.successors(root))];
+
+            while let Some((node, successors)) = calls.last_mut() {
+                let node = *node;
+                if let Some(next) = successors.next() {
+                    if let Some(next_index) = index[next] {
+                        if on_stack[next] {
+                            low_link[node] = low_link[node].min(next_index);
+                        }
+                    } else {
+                        index[next] = Some(counter);
+                        low_link[next] = counter;
+                        counter += 1;
+                        stack.push(next);
+                        on_stack[next] = true;
+                        calls.push((next, self.successors(next)));
+                    }
+                    continue;
+                }
+                calls.pop();
+                if let Some(&(parent, _)) = calls.last() {
+                    low_link[parent] = low_link[parent].min(low_link[node]);
+                }
+                // a node that can't reach anything opened before it is the root of a component
+                if Some(low_link[node]) == index[node] {
+                    let mut component = Vec::new();
+                    while let Some(member) = stack.pop() {
+                        on_stack[member] = false;
+                        component.push(member);
+                        if member == node {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+            }
+        }
+        components
+    }
+}
+
+/// Which nodes of a `Graph` are connected to each other, ignoring the direction of edges
+///
+/// This is a snapshot of the graph when it was built with `Graph::connectivity`. Connections made afterwards can be
+/// added with `connect`, but removing nodes or edges from the graph is not reflected, except that a removed node is
+/// forgotten once a node inserted into its slot is passed to `connect`.
+#[derive(Clone, Debug)]
+pub struct Connectivity<T, E = ()> {
+    graph: usize,
+    /// The first generation handed out after this was built, so any later node is known to be new
+    since: usize,
+    /// The generation of the node known in each slot, along with the element of `sets` that stands for it
+    slots: Vec<Option<(usize, usize)>>,
+    sets: UnionFind,
+    _marker: PhantomData<(T, E)>,
+}
+
+/// How a `Connectivity` sees a `WeakNode`
+enum Member {
+    /// A node it knows, with its element of the union-find
+    Known(usize),
+    /// A node inserted after it was built that it hasn't been told about
+    Unseen,
+    /// A node from another graph, or one that has since been replaced in its slot
+    Invalid,
+}
+
+impl<T, E> Connectivity<T, E> {
+    /// Record that two nodes have been connected. Nodes inserted into the graph after this `Connectivity` was built
+    /// are supported, including ones that reuse the slot of a removed node, which start out in a component of their
+    /// own.
+    ///
+    /// Returns whether the nodes were previously in different components. Nodes from another graph, and nodes whose
+    /// slot has since been taken by a node passed to `connect`, are ignored.
+    pub fn connect(&mut self, a: WeakNode<T, E>, b: WeakNode<T, E>) -> bool {
+        let (Some(a), Some(b)) = (self.track(a), self.track(b)) else {
+            return false;
+        };
+        self.sets.union(a, b)
+    }
+
+    /// Whether there is a path between two nodes, ignoring the direction of edges.
+    ///
+    /// Nodes from another graph are never in the same component, and neither are nodes whose slot has since been
+    /// taken by a node passed to `connect`.
+    #[must_use]
+    pub fn same_component(&self, a: WeakNode<T, E>, b: WeakNode<T, E>) -> bool {
+        match (self.member(a), self.member(b)) {
+            (Member::Known(a), Member::Known(b)) => self.sets.root(a) == self.sets.root(b),
+            (Member::Unseen, Member::Unseen) => a == b,
+            _ => false,
+        }
+    }
+
+    fn member(&self, node: WeakNode<T, E>) -> Member {
+        if node.graph != self.graph {
+            return Member::Invalid;
+        }
+        match self.slots.get(node.idx).copied().flatten() {
+            Some((generation, element)) if generation == node.generation => Member::Known(element),
+            Some((generation, _)) if generation > node.generation => Member::Invalid,
+            _ if node.generation >= self.since => Member::Unseen,
+            _ => Member::Invalid,
+        }
+    }
+
+    /// The element of the union-find for a node, giving a new node an element of its own
+    fn track(&mut self, node: WeakNode<T, E>) -> Option<usize> {
+        match self.member(node) {
+            Member::Known(element) => Some(element),
+            Member::Unseen => {
+                let element = self.sets.len();
+                self.sets.grow(element + 1);
+                if self.slots.len() <= node.idx {
+                    self.slots.resize(node.idx + 1, None);
+                }
+                self.slots[node.idx] = Some((node.generation, element));
+                Some(element)
+            }
+            Member::Invalid => None,
+        }
+    }
+}
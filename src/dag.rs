@@ -0,0 +1,119 @@
+use std::{
+    error::Error,
+    fmt::{Debug, Display},
+};
+
+use crate::{Directed, Graph, Kind, Node, Path};
+
+impl<T, E, K: Kind> Graph<T, E, K> {
+    /// Order the nodes so that every edge points from an earlier node to a later one.
+    ///
+    /// # Errors
+    ///
+    /// Returns one of the cycles that prevent such an order if the graph is not acyclic.
+    #[allow(clippy::type_complexity)]
+    pub fn toposort(&self) -> Result<Vec<Node<'_, T, E, K>>, Cycle<'_, T, E, K>> {
+        #[derive(Clone, Copy, PartialEq, Eq)]
+        enum Visit {
+            Unseen,
+            Open,
+            Finished,
+        }
+
+        let mut visit = vec![Visit::Unseen; self.nodes.len()];
+        let mut finished = Vec::new();
+        for root in self.indices() {
+            if visit[root] != Visit::Unseen {
+                continue;
+            }
+            visit[root] = Visit::Open;
+            // each node on the stack keeps the id of the edge it was reached by
+            let mut stack = vec![(root, None, self.edges_from(root))];
+            while let Some((node, _, edges)) = stack.last_mut() {
+                let node = *node;
+                let Some((next, id, _)) = edges.next() else {
+                    visit[node] = Visit::Finished;
+                    finished.push(node);
+                    stack.pop();
+                    continue;
+                };
+                match visit[next] {
+                    Visit::Unseen => {
+                        visit[next] = Visit::Open;
+                        stack.push((next, Some(id), self.edges_from(next)));
+                    }
+                    // an edge back to a node that is still open closes a cycle through the stack
+                    Visit::Open => {
+                        let from = stack
+                            .iter()
+                            .position(|&(idx, _, _)| idx == next)
+                            .unwrap_or_default();
+                        let mut path: Vec<usize> =
+                            stack[from..].iter().map(|&(idx, _, _)| idx).collect();
+                        path.push(next);
+                        let mut edges: Vec<usize> = stack[from + 1..]
+                            .iter()
+                            .filter_map(|&(_, via, _)| via)
+                            .collect();
+                        edges.push(id);
+                        return Err(Cycle::new(Path {
+                            graph: self,
+                            nodes: path,
+                            edges,
+                        }));
+                    }
+                    Visit::Finished => {}
+                }
+            }
+        }
+        Ok(finished
+            .into_iter()
+            .rev()
+            .map(|idx| Node { graph: self, idx })
+            .collect())
+    }
+
+    /// Whether the graph contains a cycle, including an edge from a node to itself.
+    #[must_use]
+    pub fn is_cyclic(&self) -> bool {
+        self.toposort().is_err()
+    }
+}
+
+/// A cycle in a graph that was required to have none, such as one that blocks a topological order. `NegativeCycle`
+/// wraps one for the cycles that block shortest paths.
+pub struct Cycle<'a, T, E, K = Directed> {
+    cycle: Path<'a, T, E, K>,
+}
+
+impl<'a, T, E, K: Kind> Cycle<'a, T, E, K> {
+    pub(crate) const fn new(cycle: Path<'a, T, E, K>) -> Self {
+        Self { cycle }
+    }
+
+    /// Returns the cycle as a `Path` that starts and ends at the same node
+    #[must_use]
+    pub const fn cycle(&self) -> &Path<'a, T, E, K> {
+        &self.cycle
+    }
+
+    /// Returns the cycle as a `Path` that starts and ends at the same node
+    #[must_use]
+    pub fn into_path(self) -> Path<'a, T, E, K> {
+        self.cycle
+    }
+}
+
+impl<T: Debug, E, K: Kind> Debug for Cycle<'_, T, E, K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Cycle").field(&self.cycle).finish()
+    }
+}
+
+impl<T: Debug, E, K: Kind> Display for Cycle<'_, T, E, K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "graph contains a cycle: {:?}", self.cycle)
+    }
+}
+
+impl<T: Debug, E, K: Kind> Error for Cycle<'_, T, E, K> {}
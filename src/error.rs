@@ -0,0 +1,27 @@
+use std::{error::Error, fmt::Display};
+
+/// An error from resolving a `WeakNode` or otherwise operating on a `Graph`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GraphError {
+    /// The handle was created by a different `Graph`
+    ForeignNode,
+    /// The handle refers past the end of the pool kept by the `Graph`
+    OutOfBounds,
+    /// The node the handle referred to has been removed
+    StaleNode,
+    /// The operation needs an edge between two nodes that are not connected
+    MissingEdge,
+}
+
+impl Display for GraphError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ForeignNode => write!(f, "node belongs to a different graph"),
+            Self::OutOfBounds => write!(f, "node refers outside of the graph"),
+            Self::StaleNode => write!(f, "node has been removed from the graph"),
+            Self::MissingEdge => write!(f, "nodes are not connected by an edge"),
+        }
+    }
+}
+
+impl Error for GraphError {}
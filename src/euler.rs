@@ -0,0 +1,507 @@
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fmt::Display,
+    ops::{Add, AddAssign, Div, Neg, Sub},
+};
+
+use crate::{search, weighted_matching, Directed, Graph, Kind, Node, Path, WeakNode};
+
+/// The edges leaving each slot as `(target, trail, edge)` triples, where `trail` numbers the edges to be walked and is
+/// the same at both ends of an undirected edge, and `edge` is the id of the edge of the graph that is followed
+type Trails = Vec<Vec<(usize, usize, usize)>>;
+
+impl<T, E, K: Kind> Graph<T, E, K> {
+    /// Find a circuit from `start` back to itself that follows every edge exactly once in its direction, using
+    /// Hierholzer's algorithm in O(V + E) time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start node is not part of this graph.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a node has a different number of incoming and outgoing edges, or if some edge can't be
+    /// reached from `start`.
+    pub fn eulerian_circuit(
+        &self,
+        start: Node<'_, T, E, K>,
+    ) -> Result<Path<'_, T, E, K>, EulerError<T, E>> {
+        self.check(start).unwrap_or_else(|err| {
+            panic!("Attempt to find a circuit from a node outside of graph: {err}")
+        });
+        let (trails, edges) = self.directed_trails();
+        let incoming = in_degrees(&trails);
+        if let Some(idx) = self
+            .indices()
+            .find(|&idx| incoming[idx] != trails[idx].len())
+        {
+            return Err(self.unbalanced(idx, &trails, &incoming));
+        }
+        self.hierholzer(&trails, edges, start.idx)
+    }
+
+    /// Find a walk that follows every edge exactly once in its direction, using Hierholzer's algorithm in
+    /// O(V + E) time.
+    ///
+    /// The walk is a circuit if possible, and otherwise runs from the node with one more outgoing than incoming
+    /// edge to the node with one more incoming than outgoing edge. A graph without edges gives a path holding just
+    /// its first node, or an empty path if it has no nodes either.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of incoming and outgoing edges differs by more than one at any node, or by
+    /// one at more than two nodes, or if the edges are not all connected.
+    pub fn eulerian_path(&self) -> Result<Path<'_, T, E, K>, EulerError<T, E>> {
+        let (trails, edges) = self.directed_trails();
+        let incoming = in_degrees(&trails);
+        let mut start = None;
+        let mut end = None;
+        for idx in self.indices() {
+            let outgoing = trails[idx].len();
+            let ends = if outgoing == incoming[idx] + 1 {
+                &mut start
+            } else if incoming[idx] == outgoing + 1 {
+                &mut end
+            } else if outgoing == incoming[idx] {
+                continue;
+            } else {
+                return Err(self.unbalanced(idx, &trails, &incoming));
+            };
+            if ends.replace(idx).is_some() {
+                return Err(self.unbalanced(idx, &trails, &incoming));
+            }
+        }
+        let start = start.or_else(|| self.first_with_edges(&trails));
+        self.walk_from(start, &trails, edges)
+    }
+
+    /// Find a circuit from `start` back to itself that follows every edge exactly once, ignoring direction, using
+    /// Hierholzer's algorithm in O(V + E) time.
+    ///
+    /// Every edge must be matched by one in the opposite direction, as `connect_undirected_weighted` creates, and
+    /// each such pair is walked once as a single undirected edge. Parallel edges each need a match of their own. A
+    /// loop counts twice towards the degree of its node.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start node is not part of this graph.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an edge has no matching edge back, if a node has an odd degree, or if some edge can't be
+    /// reached from `start`.
+    pub fn undirected_eulerian_circuit(
+        &self,
+        start: Node<'_, T, E, K>,
+    ) -> Result<Path<'_, T, E, K>, EulerError<T, E>> {
+        self.check(start).unwrap_or_else(|err| {
+            panic!("Attempt to find a circuit from a node outside of graph: {err}")
+        });
+        let (trails, edges) = self.undirected_trails()?;
+        if let Some(idx) = self.indices().find(|&idx| degree(&trails, idx) % 2 == 1) {
+            return Err(self.odd_degree(idx, &trails));
+        }
+        self.hierholzer(&trails, edges, start.idx)
+    }
+
+    /// Find a walk that follows every edge exactly once, ignoring direction, using Hierholzer's algorithm in
+    /// O(V + E) time.
+    ///
+    /// Edges are paired up as in `undirected_eulerian_circuit`. The walk is a circuit if possible, and otherwise
+    /// runs between the two nodes of odd degree. A graph without edges gives a path holding just its first node,
+    /// or an empty path if it has no nodes either.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an edge has no matching edge back, if more than two nodes have an odd degree, or if the
+    /// edges are not all connected.
+    pub fn undirected_eulerian_path(&self) -> Result<Path<'_, T, E, K>, EulerError<T, E>> {
+        let (trails, edges) = self.undirected_trails()?;
+        let mut odd = self.indices().filter(|&idx| degree(&trails, idx) % 2 == 1);
+        let start = odd.next();
+        if let Some(idx) = odd.nth(1) {
+            return Err(self.odd_degree(idx, &trails));
+        }
+        let start = start.or_else(|| self.first_with_edges(&trails));
+        self.walk_from(start, &trails, edges)
+    }
+
+    /// Find the shortest closed walk from `start` that follows every edge at least once, ignoring direction, by
+    /// solving the Chinese postman problem.
+    ///
+    /// Edges are paired up as in `undirected_eulerian_circuit`, and both edges of a pair should have the same
+    /// weight. The nodes of odd degree are paired by a minimum weight perfect matching on their shortest path
+    /// distances, the shortest paths between each pair are walked twice, and the result is an Eulerian circuit.
+    /// This takes O(V³ + V (V + E) log V) time. The matching keeps dual variables that can go below zero, so `E`
+    /// has to be a signed type, as the `Neg` bound ensures, and it halves some of them, which is exact for integers
+    /// as the values halved are always even.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start node is not part of this graph.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an edge has no matching edge back, or if some edge can't be reached from `start`.
+    pub fn chinese_postman(
+        &self,
+        start: Node<'_, T, E, K>,
+    ) -> Result<PostmanTour<'_, T, E, K>, EulerError<T, E>>
+    where
+        E: Default
+            + Clone
+            + Ord
+            + Add<E, Output = E>
+            + AddAssign<E>
+            + Sub<E, Output = E>
+            + Div<E, Output = E>
+            + Neg<Output = E>
+            + From<u8>,
+    {
+        self.check(start).unwrap_or_else(|err| {
+            panic!("Attempt to find a tour from a node outside of graph: {err}")
+        });
+        let (mut trails, mut edges) = self.undirected_trails()?;
+        let odd: Vec<usize> = self
+            .indices()
+            .filter(|&idx| degree(&trails, idx) % 2 == 1)
+            .collect();
+        let searches: Vec<_> = odd
+            .iter()
+            .map(|&idx| {
+                search::dijkstra(self.nodes.len(), idx, None, |node| {
+                    self.weighted_edges(node)
+                })
+            })
+            .collect();
+        let mut distances = Vec::new();
+        for (i, search) in searches.iter().enumerate() {
+            for (j, &other) in odd.iter().enumerate().skip(i + 1) {
+                if let Some(distance) = &search.distance[other] {
+                    distances.push((i, j, distance.clone()));
+                }
+            }
+        }
+        // each component has an even number of odd nodes, so they can only fail to pair across components
+        let Some(mate) = weighted_matching::minimum_weight_perfect_matching(odd.len(), distances)
+        else {
+            return Err(EulerError::Disconnected {
+                node: self.handle(odd[0]),
+            });
+        };
+
+        for (i, search) in searches.iter().enumerate() {
+            if i < mate[i] {
+                let path = search.path(self, odd[i], odd[mate[i]]);
+                for (pair, &id) in path
+                    .iter()
+                    .flat_map(|path| path.nodes.windows(2).zip(&path.edges))
+                {
+                    let back = self
+                        .cheapest_edge_id(pair[1], pair[0])
+                        .expect("every edge has been matched by one back");
+                    trails[pair[0]].push((pair[1], edges, id));
+                    trails[pair[1]].push((pair[0], edges, back));
+                    edges += 1;
+                }
+            }
+        }
+        let path = self.hierholzer(&trails, edges, start.idx)?;
+        let weight = path.len();
+        Ok(PostmanTour { path, weight })
+    }
+
+    /// Every edge as its own trail, along with the number of edges
+    fn directed_trails(&self) -> (Trails, usize) {
+        let mut trails = vec![Vec::new(); self.nodes.len()];
+        let mut edges = 0;
+        for idx in self.indices() {
+            for (next, id, _) in self.edges_from(idx) {
+                trails[idx].push((next, edges, id));
+                edges += 1;
+            }
+        }
+        (trails, edges)
+    }
+
+    /// Every pair of opposite edges as a single trail usable from both ends, along with the number of pairs. Parallel
+    /// edges are paired one for one in the order they were added.
+    fn undirected_trails(&self) -> Result<(Trails, usize), EulerError<T, E>> {
+        let mut arcs: BTreeMap<(usize, usize), Vec<usize>> = BTreeMap::new();
+        for idx in self.indices() {
+            for (next, id, _) in self.edges_from(idx) {
+                arcs.entry((idx, next)).or_default().push(id);
+            }
+        }
+        let mut trails = vec![Vec::new(); self.nodes.len()];
+        let mut edges = 0;
+        for (&(idx, next), ids) in &arcs {
+            if idx == next {
+                for &id in ids {
+                    trails[idx].push((idx, edges, id));
+                    edges += 1;
+                }
+                continue;
+            }
+            let backs = arcs.get(&(next, idx)).map_or(&[][..], Vec::as_slice);
+            if backs.len() < ids.len() {
+                return Err(EulerError::OneWay {
+                    start: self.handle(idx),
+                    end: self.handle(next),
+                });
+            }
+            if idx < next {
+                for (&id, &back) in ids.iter().zip(backs) {
+                    trails[idx].push((next, edges, id));
+                    trails[next].push((idx, edges, back));
+                    edges += 1;
+                }
+            }
+        }
+        Ok((trails, edges))
+    }
+
+    /// The first node with any edges, or else the first node at all
+    fn first_with_edges(&self, trails: &Trails) -> Option<usize> {
+        self.indices()
+            .find(|&idx| !trails[idx].is_empty())
+            .or_else(|| self.indices().next())
+    }
+
+    /// Walk every trail from `start`, or return an empty path if the graph has no nodes
+    fn walk_from(
+        &self,
+        start: Option<usize>,
+        trails: &Trails,
+        edges: usize,
+    ) -> Result<Path<'_, T, E, K>, EulerError<T, E>> {
+        start.map_or_else(
+            || {
+                Ok(Path {
+                    graph: self,
+                    nodes: Vec::new(),
+                    edges: Vec::new(),
+                })
+            },
+            |start| self.hierholzer(trails, edges, start),
+        )
+    }
+
+    /// Splice together closed walks from `start` until every trail has been followed, building the walk in
+    /// reverse on the way back out of the search
+    fn hierholzer(
+        &self,
+        trails: &Trails,
+        edges: usize,
+        start: usize,
+    ) -> Result<Path<'_, T, E, K>, EulerError<T, E>> {
+        let mut used = vec![false; edges];
+        let mut next_trail = vec![0; self.nodes.len()];
+        // each node on the stack keeps the id of the edge it was reached by, which is the edge taken to it in the
+        // finished walk
+        let mut stack = vec![(start, None)];
+        let mut walk = Vec::with_capacity(edges + 1);
+        let mut taken = Vec::with_capacity(edges);
+        while let Some(&(node, via)) = stack.last() {
+            if let Some(&(next, trail, id)) = trails[node].get(next_trail[node]) {
+                next_trail[node] += 1;
+                if !used[trail] {
+                    used[trail] = true;
+                    stack.push((next, Some(id)));
+                }
+            } else {
+                walk.push(node);
+                taken.extend(via);
+                stack.pop();
+            }
+        }
+        if let Some(idx) = self
+            .indices()
+            .find(|&idx| trails[idx].iter().any(|&(_, trail, _)| !used[trail]))
+        {
+            return Err(EulerError::Disconnected {
+                node: self.handle(idx),
+            });
+        }
+        walk.reverse();
+        taken.reverse();
+        Ok(Path {
+            graph: self,
+            nodes: walk,
+            edges: taken,
+        })
+    }
+
+    fn unbalanced(&self, idx: usize, trails: &Trails, incoming: &[usize]) -> EulerError<T, E> {
+        EulerError::Unbalanced {
+            node: self.handle(idx),
+            incoming: incoming[idx],
+            outgoing: trails[idx].len(),
+        }
+    }
+
+    fn odd_degree(&self, idx: usize, trails: &Trails) -> EulerError<T, E> {
+        EulerError::OddDegree {
+            node: self.handle(idx),
+            degree: degree(trails, idx),
+        }
+    }
+}
+
+fn in_degrees(trails: &Trails) -> Vec<usize> {
+    let mut incoming = vec![0; trails.len()];
+    for &(next, _, _) in trails.iter().flatten() {
+        incoming[next] += 1;
+    }
+    incoming
+}
+
+/// The number of undirected edge ends at `idx`, counting both ends of a loop
+fn degree(trails: &Trails, idx: usize) -> usize {
+    trails[idx]
+        .iter()
+        .map(|&(next, _, _)| if next == idx { 2 } else { 1 })
+        .sum()
+}
+
+/// A shortest closed walk that covers every edge, found by `Graph::chinese_postman`
+pub struct PostmanTour<'a, T, E, K = Directed> {
+    path: Path<'a, T, E, K>,
+    weight: E,
+}
+
+impl<'a, T, E, K: Kind> PostmanTour<'a, T, E, K> {
+    /// Returns the walk, which starts and ends at the same node
+    #[must_use]
+    pub const fn path(&self) -> &Path<'a, T, E, K> {
+        &self.path
+    }
+
+    /// Returns the walk, which starts and ends at the same node
+    #[must_use]
+    pub fn into_path(self) -> Path<'a, T, E, K> {
+        self.path
+    }
+
+    /// Returns the total weight of the walk, counting edges walked twice twice
+    #[must_use]
+    pub const fn weight(&self) -> &E {
+        &self.weight
+    }
+}
+
+/// The degree condition that stops a graph from having an Eulerian path or circuit
+pub enum EulerError<T, E> {
+    /// A node has a number of incoming and outgoing edges that the walk can't use up, either because they differ
+    /// by too much or because other nodes already start or end the walk
+    Unbalanced {
+        node: WeakNode<T, E>,
+        incoming: usize,
+        outgoing: usize,
+    },
+    /// A node touches an odd number of undirected edges, where a circuit allows none and a path allows two
+    OddDegree { node: WeakNode<T, E>, degree: usize },
+    /// An edge has no edge back, so it can't be walked as an undirected edge
+    OneWay {
+        start: WeakNode<T, E>,
+        end: WeakNode<T, E>,
+    },
+    /// A node has edges that can't be reached from where the walk starts
+    Disconnected { node: WeakNode<T, E> },
+}
+
+impl<T, E> Clone for EulerError<T, E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, E> Copy for EulerError<T, E> {}
+
+impl<T, E> PartialEq for EulerError<T, E> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                Self::Unbalanced {
+                    node,
+                    incoming,
+                    outgoing,
+                },
+                Self::Unbalanced {
+                    node: other_node,
+                    incoming: other_incoming,
+                    outgoing: other_outgoing,
+                },
+            ) => node == other_node && incoming == other_incoming && outgoing == other_outgoing,
+            (
+                Self::OddDegree { node, degree },
+                Self::OddDegree {
+                    node: other_node,
+                    degree: other_degree,
+                },
+            ) => node == other_node && degree == other_degree,
+            (
+                Self::OneWay { start, end },
+                Self::OneWay {
+                    start: other_start,
+                    end: other_end,
+                },
+            ) => start == other_start && end == other_end,
+            (Self::Disconnected { node }, Self::Disconnected { node: other_node }) => {
+                node == other_node
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<T, E> Eq for EulerError<T, E> {}
+
+impl<T, E> std::fmt::Debug for EulerError<T, E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unbalanced {
+                node,
+                incoming,
+                outgoing,
+            } => f
+                .debug_struct("Unbalanced")
+                .field("node", node)
+                .field("incoming", incoming)
+                .field("outgoing", outgoing)
+                .finish(),
+            Self::OddDegree { node, degree } => f
+                .debug_struct("OddDegree")
+                .field("node", node)
+                .field("degree", degree)
+                .finish(),
+            Self::OneWay { start, end } => f
+                .debug_struct("OneWay")
+                .field("start", start)
+                .field("end", end)
+                .finish(),
+            Self::Disconnected { node } => {
+                f.debug_struct("Disconnected").field("node", node).finish()
+            }
+        }
+    }
+}
+
+impl<T, E> Display for EulerError<T, E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unbalanced {
+                incoming, outgoing, ..
+            } => write!(
+                f,
+                "a node has {incoming} incoming and {outgoing} outgoing edges"
+            ),
+            Self::OddDegree { degree, .. } => write!(f, "a node has odd degree {degree}"),
+            Self::OneWay { .. } => write!(f, "an edge has no matching edge in the other direction"),
+            Self::Disconnected { .. } => write!(f, "not every edge can be reached from the start"),
+        }
+    }
+}
+
+impl<T, E> Error for EulerError<T, E> {}
@@ -0,0 +1,307 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    ops::{Add, Sub},
+};
+
+use crate::{Directed, EdgeHandle, Graph, Kind, Node, WeakNode};
+
+impl<T, E, K: Kind> Graph<T, E, K> {
+    /// Find a maximum flow from `source` to `sink` with Dinic's algorithm, using edge weights as capacities.
+    ///
+    /// Dinic's algorithm saturates every shortest augmenting path at once, taking O(V² E) time at worst and much less
+    /// in practice. See `max_flow_edmonds_karp` for a simpler alternative.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either the source or sink node is not part of this graph.
+    #[must_use]
+    pub fn max_flow(
+        &self,
+        source: Node<'_, T, E, K>,
+        sink: Node<'_, T, E, K>,
+    ) -> MaxFlow<'_, T, E, K>
+    where
+        E: Default + Clone + Ord + Add<E, Output = E> + Sub<E, Output = E>,
+    {
+        self.flow_network(source, sink).dinic()
+    }
+
+    /// Find a maximum flow from `source` to `sink` with the Edmonds-Karp algorithm, using edge weights as
+    /// capacities.
+    ///
+    /// Edmonds-Karp augments along one shortest path at a time, taking O(V E²) time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either the source or sink node is not part of this graph.
+    #[must_use]
+    pub fn max_flow_edmonds_karp(
+        &self,
+        source: Node<'_, T, E, K>,
+        sink: Node<'_, T, E, K>,
+    ) -> MaxFlow<'_, T, E, K>
+    where
+        E: Default + Clone + Ord + Add<E, Output = E> + Sub<E, Output = E>,
+    {
+        self.flow_network(source, sink).edmonds_karp()
+    }
+
+    fn flow_network(
+        &self,
+        source: Node<'_, T, E, K>,
+        sink: Node<'_, T, E, K>,
+    ) -> FlowNetwork<'_, T, E, K>
+    where
+        E: Default + Clone,
+    {
+        for node in [source, sink] {
+            self.check(node).unwrap_or_else(|err| {
+                panic!("Attempt to find flow for node outside of graph: {err}")
+            });
+        }
+        let mut residual = Residual {
+            to: Vec::new(),
+            capacity: Vec::new(),
+            outgoing: vec![Vec::new(); self.nodes.len()],
+        };
+        let mut edges = Vec::new();
+        for idx in self.indices() {
+            for (next, id, capacity) in self.weighted_edges(idx) {
+                // a loop can't carry flow anywhere
+                if next != idx {
+                    edges.push((idx, next, id, residual.push(idx, next, capacity)));
+                }
+            }
+        }
+        FlowNetwork {
+            graph: self,
+            source: source.idx,
+            sink: sink.idx,
+            residual,
+            edges,
+        }
+    }
+}
+
+/// The residual capacities left by a flow, as pairs of arcs so that the reverse of arc `i` is arc `i ^ 1`
+pub struct Residual<C> {
+    pub to: Vec<usize>,
+    pub capacity: Vec<C>,
+    pub outgoing: Vec<Vec<usize>>,
+}
+
+impl<C: Default> Residual<C> {
+    /// Add an edge and its empty reverse arc, returning the index of the forward arc
+    pub fn push(&mut self, start: usize, end: usize, capacity: C) -> usize {
+        let arc = self.to.len();
+        self.to.extend([end, start]);
+        self.capacity.extend([capacity, C::default()]);
+        self.outgoing[start].push(arc);
+        self.outgoing[end].push(arc ^ 1);
+        arc
+    }
+}
+
+impl<C: Default + Clone + Ord + Add<C, Output = C> + Sub<C, Output = C>> Residual<C> {
+    /// Push `amount` more flow along each of the arcs
+    pub fn augment(&mut self, arcs: &[usize], amount: &C) {
+        for &arc in arcs {
+            self.capacity[arc] = self.capacity[arc].clone() - amount.clone();
+            self.capacity[arc ^ 1] = self.capacity[arc ^ 1].clone() + amount.clone();
+        }
+    }
+
+    /// The smallest residual capacity among the arcs
+    pub fn bottleneck(&self, arcs: &[usize]) -> Option<C> {
+        arcs.iter().map(|&arc| &self.capacity[arc]).min().cloned()
+    }
+
+    /// The number of arcs between each node and `source` along arcs with capacity left, or `None` if unreachable
+    pub fn levels(&self, source: usize) -> Vec<Option<usize>> {
+        let mut level = vec![None; self.outgoing.len()];
+        level[source] = Some(0);
+        let mut queue = VecDeque::from([source]);
+        while let Some(node) = queue.pop_front() {
+            for &arc in &self.outgoing[node] {
+                let next = self.to[arc];
+                if level[next].is_none() && self.capacity[arc] > C::default() {
+                    level[next] = level[node].map(|level| level + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+        level
+    }
+}
+
+/// A flow problem on a `Graph`, with the residual network of the flow found so far
+struct FlowNetwork<'a, T, E, K> {
+    graph: &'a Graph<T, E, K>,
+    source: usize,
+    sink: usize,
+    residual: Residual<E>,
+    /// The start, end, id and forward arc of every edge that can carry flow
+    edges: Vec<(usize, usize, usize, usize)>,
+}
+
+impl<'a, T, E: Default + Clone + Ord + Add<E, Output = E> + Sub<E, Output = E>, K: Kind>
+    FlowNetwork<'a, T, E, K>
+{
+    fn edmonds_karp(mut self) -> MaxFlow<'a, T, E, K> {
+        let mut value = E::default();
+        while self.source != self.sink {
+            // breadth first search for the shortest path with capacity left
+            let mut via = vec![None; self.residual.outgoing.len()];
+            let mut queue = VecDeque::from([self.source]);
+            while let Some(node) = queue.pop_front() {
+                for &arc in &self.residual.outgoing[node] {
+                    let next = self.residual.to[arc];
+                    if next != self.source
+                        && via[next].is_none()
+                        && self.residual.capacity[arc] > E::default()
+                    {
+                        via[next] = Some(arc);
+                        queue.push_back(next);
+                    }
+                }
+            }
+            let mut path = Vec::new();
+            let mut node = self.sink;
+            while let Some(arc) = via[node] {
+                path.push(arc);
+                node = self.residual.to[arc ^ 1];
+            }
+            let Some(amount) = self.residual.bottleneck(&path) else {
+                break;
+            };
+            self.residual.augment(&path, &amount);
+            value = value + amount;
+        }
+        self.finish(value)
+    }
+
+    fn dinic(mut self) -> MaxFlow<'a, T, E, K> {
+        let mut value = E::default();
+        while self.source != self.sink {
+            let level = self.residual.levels(self.source);
+            if level[self.sink].is_none() {
+                break;
+            }
+            // find a blocking flow by depth first search along arcs that lead one level further, remembering
+            // which arcs of each node have been used up
+            let mut next_arc = vec![0; self.residual.outgoing.len()];
+            let mut path: Vec<usize> = Vec::new();
+            loop {
+                let node = path
+                    .last()
+                    .map_or(self.source, |&arc| self.residual.to[arc]);
+                if node == self.sink {
+                    let amount = self.residual.bottleneck(&path).unwrap_or_default();
+                    self.residual.augment(&path, &amount);
+                    value = value + amount;
+                    // resume from the tail of the first arc that was saturated
+                    let saturated = path
+                        .iter()
+                        .position(|&arc| self.residual.capacity[arc] == E::default())
+                        .unwrap_or_default();
+                    path.truncate(saturated);
+                    continue;
+                }
+                let outgoing = &self.residual.outgoing[node];
+                while let Some(&arc) = outgoing.get(next_arc[node]) {
+                    let next = self.residual.to[arc];
+                    if self.residual.capacity[arc] > E::default()
+                        && level[next] == level[node].map(|level| level + 1)
+                    {
+                        break;
+                    }
+                    next_arc[node] += 1;
+                }
+                if let Some(&arc) = outgoing.get(next_arc[node]) {
+                    path.push(arc);
+                } else if let Some(arc) = path.pop() {
+                    // a dead end, so don't try this arc again
+                    next_arc[self.residual.to[arc ^ 1]] += 1;
+                } else {
+                    break;
+                }
+            }
+        }
+        self.finish(value)
+    }
+
+    fn finish(self, value: E) -> MaxFlow<'a, T, E, K> {
+        // once the flow is maximal, whatever the source can still reach is one side of a minimum cut
+        let level = self.residual.levels(self.source);
+        MaxFlow {
+            graph: self.graph,
+            value,
+            flow: self
+                .edges
+                .into_iter()
+                .map(|(start, end, id, arc)| {
+                    ((start, end, id), self.residual.capacity[arc ^ 1].clone())
+                })
+                .collect(),
+            source_side: self
+                .graph
+                .indices()
+                .filter(|&idx| level[idx].is_some())
+                .collect(),
+        }
+    }
+}
+
+/// A maximum flow through a `Graph` whose edge weights are capacities
+pub struct MaxFlow<'a, T, E, K = Directed> {
+    graph: &'a Graph<T, E, K>,
+    value: E,
+    /// The flow along every edge, by its start, end and id
+    flow: BTreeMap<(usize, usize, usize), E>,
+    source_side: Vec<usize>,
+}
+
+impl<T, E, K: Kind> MaxFlow<'_, T, E, K> {
+    /// Returns the total amount of flow from the source to the sink
+    #[must_use]
+    pub const fn value(&self) -> &E {
+        &self.value
+    }
+
+    /// Returns the flow along the edge from `start` to `end`, or `None` if there is no such edge. If there are
+    /// parallel edges, this is the flow along the earliest of them, and `flow_on` gives the flow along each.
+    #[must_use]
+    pub fn flow(&self, start: WeakNode<T, E>, end: WeakNode<T, E>) -> Option<&E> {
+        let start = self.graph.resolve(start).ok()?;
+        let end = self.graph.resolve(end).ok()?;
+        self.flow
+            .range((start, end, 0)..=(start, end, usize::MAX))
+            .next()
+            .map(|(_, flow)| flow)
+    }
+
+    /// Returns the flow along a single edge, or `None` if it is not an edge of the graph.
+    #[must_use]
+    pub fn flow_on(&self, edge: EdgeHandle<T, E>) -> Option<&E> {
+        let start = self.graph.resolve(edge.start).ok()?;
+        let end = self.graph.resolve(edge.end).ok()?;
+        self.flow.get(&(start, end, edge.id))
+    }
+
+    /// Returns an iterator over every edge that can carry flow along with the flow it carries
+    pub fn edge_flows(&self) -> impl Iterator<Item = (WeakNode<T, E>, WeakNode<T, E>, &E)> {
+        self.flow.iter().map(|(&(start, end, _), flow)| {
+            (self.graph.handle(start), self.graph.handle(end), flow)
+        })
+    }
+
+    /// Returns the nodes on the source side of a minimum cut. The edges leaving this set are saturated, and their
+    /// capacities add up to the value of the flow.
+    #[must_use]
+    pub fn min_cut(&self) -> BTreeSet<WeakNode<T, E>> {
+        self.source_side
+            .iter()
+            .map(|&idx| self.graph.handle(idx))
+            .collect()
+    }
+}
@@ -0,0 +1,42 @@
+/// How a `Graph` treats its edges, fixed by its type so a graph can't change kind by being cloned or copied.
+///
+/// `Directed` is the kind of a plain `Graph`. The other kinds are only found inside the wrapper that maintains them,
+/// and are reached through its `Deref`.
+pub trait Kind: sealed::Sealed {
+    /// Whether each edge is stored once at its start and followed from both ends
+    const UNDIRECTED: bool;
+    /// Whether every connection adds a new edge instead of replacing the weight of one already there
+    const MULTI: bool;
+}
+
+/// The kind of a plain `Graph`, whose edges lead from their start to their end
+pub enum Directed {}
+
+/// The kind of the graph inside an `UnGraph`, whose edges lead both ways
+pub enum Undirected {}
+
+/// The kind of the graph inside a `MultiGraph`, which keeps parallel edges
+pub enum Multi {}
+
+impl Kind for Directed {
+    const UNDIRECTED: bool = false;
+    const MULTI: bool = false;
+}
+
+impl Kind for Undirected {
+    const UNDIRECTED: bool = true;
+    const MULTI: bool = false;
+}
+
+impl Kind for Multi {
+    const UNDIRECTED: bool = false;
+    const MULTI: bool = true;
+}
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for super::Directed {}
+    impl Sealed for super::Undirected {}
+    impl Sealed for super::Multi {}
+}
@@ -1,41 +1,133 @@
 #![warn(clippy::pedantic, clippy::nursery)]
 use std::{
     cmp::Ordering,
-    collections::{btree_map, BTreeMap, BTreeSet, VecDeque},
+    collections::{btree_map, btree_set, BTreeMap, BTreeSet, VecDeque},
     fmt::Debug,
+    hash::{Hash, Hasher},
+    iter,
     marker::PhantomData,
-    ops::{Add, AddAssign, Deref, DerefMut},
+    ops::{Add, AddAssign, Deref, DerefMut, Index, IndexMut, Range},
     slice,
+    sync::atomic::{self, AtomicUsize},
 };
 
-#[derive(Clone, Default)]
-pub struct Graph<T, E = ()> {
-    nodes: Vec<Adjacency<T, E>>,
+mod all_pairs;
+mod astar;
+mod biconnected;
+mod blossom;
+mod components;
+mod dag;
+mod error;
+mod euler;
+mod flow;
+mod kind;
+mod matching;
+mod min_cost_flow;
+mod multigraph;
+mod search;
+mod shortest_paths;
+mod spanning_tree;
+mod ungraph;
+mod union_find;
+mod weighted_matching;
+
+pub use all_pairs::DistanceMatrix;
+pub use astar::AStar;
+pub use components::Connectivity;
+pub use dag::Cycle;
+pub use error::GraphError;
+pub use euler::{EulerError, PostmanTour};
+pub use flow::MaxFlow;
+pub use kind::{Directed, Kind, Multi, Undirected};
+pub use matching::{Assignment, AssignmentError, OddCycle};
+pub use min_cost_flow::{CostEdge, MinCostFlow};
+pub use multigraph::MultiGraph;
+pub use shortest_paths::{NegativeCycle, Settled, ShortestPaths};
+pub use ungraph::UnGraph;
+
+/// Source of the identities that let a `Graph` recognize its own `WeakNode`s
+static NEXT_GRAPH_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Source of the generations that tell apart every node ever inserted into any graph
+static NEXT_GENERATION: AtomicUsize = AtomicUsize::new(0);
+
+/// A directed graph whose nodes hold a `T` and whose edges hold an `E`.
+///
+/// Cloning a graph gives the clone an identity of its own, so `WeakNode`s from the original are rejected by it. Use
+/// `translate` to find the node of the clone that a `WeakNode` of the original refers to.
+///
+/// The kind `K` is `Directed` for every graph built with `Graph::new`. An `UnGraph` holds a `Graph` of kind
+/// `Undirected` and a `MultiGraph` one of kind `Multi`, and clones of them keep that kind.
+pub struct Graph<T, E = (), K = Directed> {
+    id: usize,
+    nodes: Vec<Slot<T, E>>,
+    vacant: Vec<usize>,
+    /// The id given to the next edge of a `MultiGraph`, never reused so stale `EdgeHandle`s can be recognized
+    next_edge: usize,
+    kind: PhantomData<K>,
+}
+
+impl<T: Clone, E: Clone, K: Kind> Clone for Graph<T, E, K> {
+    fn clone(&self) -> Self {
+        Self {
+            id: NEXT_GRAPH_ID.fetch_add(1, atomic::Ordering::Relaxed),
+            nodes: self.nodes.clone(),
+            vacant: self.vacant.clone(),
+            next_edge: self.next_edge,
+            kind: PhantomData,
+        }
+    }
+}
+
+impl<T, E> Default for Graph<T, E> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl<T, E> Graph<T, E> {
+    #[must_use]
+    /// Create a graph with no nodes
+    pub fn new() -> Self {
+        Self::empty()
+    }
+}
+
+impl<T, E, K: Kind> Graph<T, E, K> {
     /// Connect two nodes with a weight
     ///
     /// # Panics
     ///
-    /// Panics if either the start or end node refers outside of the pool kept by the graph.
+    /// Panics if either the start or end node is not a live node of this graph.
     pub fn connect_weighted(&mut self, start: WeakNode<T, E>, end: WeakNode<T, E>, weight: E) {
-        assert!(
-            start.0 < self.nodes.len(),
-            "Attempt to create connection with a node that is not part of this graph."
-        );
-        assert!(
-            end.0 < self.nodes.len(),
-            "Attempt to create connection with a node that is not part of this graph."
-        );
-        self.nodes[start.0].edges.insert(end.0, weight);
+        self.try_connect_weighted(start, end, weight)
+            .unwrap_or_else(|err| {
+                panic!("Attempt to create connection with a node that is not part of this graph: {err}")
+            });
+    }
+
+    /// Connect two nodes with a weight
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either the start or end node is not a live node of this graph.
+    pub fn try_connect_weighted(
+        &mut self,
+        start: WeakNode<T, E>,
+        end: WeakNode<T, E>,
+        weight: E,
+    ) -> Result<(), GraphError> {
+        let start = self.resolve(start)?;
+        let end = self.resolve(end)?;
+        self.add_edge(start, end, weight);
+        Ok(())
     }
 
     /// Connect two nodes with a weight, using a bidirectional connection
     ///
     /// # Panics
     ///
-    /// Panics if either the start or end node refers outside of the pool kept by the graph.
+    /// Panics if either the start or end node is not a live node of this graph.
     pub fn connect_undirected_weighted(
         &mut self,
         start: WeakNode<T, E>,
@@ -44,67 +136,207 @@ impl<T, E> Graph<T, E> {
     ) where
         E: Clone,
     {
-        self.connect_weighted(start, end, weight.clone());
-        self.connect_weighted(end, start, weight);
+        self.try_connect_undirected_weighted(start, end, weight)
+            .unwrap_or_else(|err| {
+                panic!("Attempt to create connection with a node that is not part of this graph: {err}")
+            });
+    }
+
+    /// Connect two nodes with a weight, using a bidirectional connection
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either the start or end node is not a live node of this graph. No connection is made in
+    /// that case.
+    pub fn try_connect_undirected_weighted(
+        &mut self,
+        start: WeakNode<T, E>,
+        end: WeakNode<T, E>,
+        weight: E,
+    ) -> Result<(), GraphError>
+    where
+        E: Clone,
+    {
+        let start = self.resolve(start)?;
+        let end = self.resolve(end)?;
+        self.add_edge(start, end, weight.clone());
+        self.add_edge(end, start, weight);
+        Ok(())
+    }
+
+    /// Remove the connection from `start` to `end`, returning its weight if it existed.
+    ///
+    /// Only the directed edge is removed; an undirected connection needs to be disconnected in both directions.
+    pub fn disconnect(&mut self, start: WeakNode<T, E>, end: WeakNode<T, E>) -> Option<E> {
+        let start = self.resolve(start).ok()?;
+        let end = self.resolve(end).ok()?;
+        let (start, end, id) = self.edge_id(start, end)?;
+        self.remove_edge(start, end, id)
+    }
+
+    /// Returns the weight of the edge from `start` to `end`, if both are live nodes of this graph and the edge exists.
+    #[must_use]
+    pub fn edge_weight(&self, start: WeakNode<T, E>, end: WeakNode<T, E>) -> Option<&E> {
+        let start = self.resolve(start).ok()?;
+        let end = self.resolve(end).ok()?;
+        self.edge(start, end)
+    }
+
+    /// Returns the weight of the edge from `start` to `end` mutably, if both are live nodes of this graph and the
+    /// edge exists.
+    ///
+    /// Only the directed edge is returned; the two directions of an undirected connection have separate weights.
+    pub fn edge_weight_mut(
+        &mut self,
+        start: WeakNode<T, E>,
+        end: WeakNode<T, E>,
+    ) -> Option<&mut E> {
+        let start = self.resolve(start).ok()?;
+        let end = self.resolve(end).ok()?;
+        let (start, end, id) = self.edge_id(start, end)?;
+        self.adjacency_mut(start).edges.get_mut(&(end, id))
+    }
+
+    /// Returns an iterator over every edge of the graph, ordered by their start and then their end node.
+    #[must_use]
+    pub const fn edges(&self) -> Edges<'_, T, E, K> {
+        Edges {
+            graph: self,
+            sources: 0..self.nodes.len(),
+            source: 0,
+            current: None,
+            incoming: None,
+        }
+    }
+
+    /// Remove a node and every connection to or from it, returning its value.
+    ///
+    /// Weak references to other nodes remain valid. The slot of the removed node may be reused by a later `insert`,
+    /// but weak references to the removed node will never resolve to the new occupant.
+    pub fn remove_node(&mut self, node: WeakNode<T, E>) -> Option<T> {
+        let idx = self.resolve(node).ok()?;
+        let removed = self.nodes[idx].adjacency.take()?;
+        // the slot is already empty, so a loop back to the removed node is skipped
+        for &(end, id) in removed.edges.keys() {
+            if let Some(adjacency) = &mut self.nodes[end].adjacency {
+                adjacency.incoming.remove(&(idx, id));
+            }
+        }
+        for &(start, id) in &removed.incoming {
+            if let Some(adjacency) = &mut self.nodes[start].adjacency {
+                adjacency.edges.remove(&(idx, id));
+            }
+        }
+        self.vacant.push(idx);
+        Some(removed.value)
     }
 
+    /// Returns some node of the graph, or `None` if the graph is empty.
     #[must_use]
-    pub const fn arbitrary_node(&self) -> Node<'_, T, E> {
-        Node {
+    pub fn arbitrary_node(&self) -> Option<Node<'_, T, E, K>> {
+        Some(Node {
             graph: self,
-            idx: 0,
+            idx: self.indices().next()?,
+        })
+    }
+
+    /// Returns an iterator over every node of the graph, in the order of their slots.
+    #[must_use]
+    pub const fn nodes(&self) -> Nodes<'_, T, E, K> {
+        Nodes {
+            graph: self,
+            slots: 0..self.nodes.len(),
         }
     }
 
+    /// Returns an iterator over the value of every node of the graph mutably, along with its weak reference, in the
+    /// order of their slots.
+    pub fn iter_mut(&mut self) -> IterMut<'_, T, E> {
+        IterMut {
+            graph: self.id,
+            slots: self.nodes.iter_mut().enumerate(),
+        }
+    }
+
+    /// Returns the number of nodes in the graph.
+    #[must_use]
+    pub const fn node_count(&self) -> usize {
+        self.nodes.len() - self.vacant.len()
+    }
+
+    /// Returns the number of edges in the graph, counting both directions of a connection made by
+    /// `connect_undirected_weighted` but each edge of an `UnGraph` once.
+    #[must_use]
+    pub fn edge_count(&self) -> usize {
+        self.indices()
+            .map(|idx| self.adjacency(idx).edges.len())
+            .sum()
+    }
+
+    /// Returns whether the graph has no nodes.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.node_count() == 0
+    }
+
     /// Find one node in the graph whose content equals the provided value.
-    pub fn find(&self, item: &T) -> Option<Node<'_, T, E>>
+    pub fn find(&self, item: &T) -> Option<Node<'_, T, E, K>>
     where
         T: PartialEq,
     {
         Some(Node {
             graph: self,
-            idx: self.nodes.iter().position(|p| &p.value == item)?,
+            idx: self
+                .indices()
+                .find(|&idx| &self.adjacency(idx).value == item)?,
         })
     }
 
     /// Convert a weak reference to a strong reference. See `WeakNode` and `Node` for more information.
     ///
-    /// This will cause unexpected behavior if the provided `WeakNode` is not from this graph
-    /// or if the graph has changed since the `WeakNode` reference was created.
-    ///
     /// # Panics
     ///
-    /// Panics if the node would return a reference outside of the pool kept by the graph.
+    /// Panics if the `WeakNode` is from a different graph or refers to a node that has been removed.
     #[must_use]
-    pub fn weak_ref(&self, node: WeakNode<T, E>) -> Node<'_, T, E> {
-        assert!(
-            node.0 < self.nodes.len(),
-            "Attempt to use a weak ref past end of graph"
-        );
-        Node {
+    pub fn weak_ref(&self, node: WeakNode<T, E>) -> Node<'_, T, E, K> {
+        self.try_weak_ref(node)
+            .unwrap_or_else(|err| panic!("Attempt to use an invalid weak ref: {err}"))
+    }
+
+    /// Convert a weak reference to a strong reference. See `WeakNode` and `Node` for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `WeakNode` is from a different graph or refers to a node that has been removed.
+    pub fn try_weak_ref(&self, node: WeakNode<T, E>) -> Result<Node<'_, T, E, K>, GraphError> {
+        Ok(Node {
             graph: self,
-            idx: node.0,
-        }
+            idx: self.resolve(node)?,
+        })
     }
 
     /// Convert a weak reference to a strong mutable reference. See `WeakNode` and `NodeMut` for more information.
     ///
-    /// This will cause unexpected behavior if the provided `WeakNode` is not from this graph
-    /// or if the graph has changed since the `WeakNode` reference was created.
-    ///
     /// # Panics
     ///
-    /// Panics if the node would return a reference outside of the pool kept by the graph.
+    /// Panics if the `WeakNode` is from a different graph or refers to a node that has been removed.
     #[must_use]
-    pub fn weak_mut(&mut self, node: WeakNode<T, E>) -> NodeMut<'_, T, E> {
-        assert!(
-            node.0 < self.nodes.len(),
-            "Attempt to use a weak ref past end of graph"
-        );
-        NodeMut {
-            graph: self,
-            idx: node.0,
-        }
+    pub fn weak_mut(&mut self, node: WeakNode<T, E>) -> NodeMut<'_, T, E, K> {
+        self.try_weak_mut(node)
+            .unwrap_or_else(|err| panic!("Attempt to use an invalid weak ref: {err}"))
+    }
+
+    /// Convert a weak reference to a strong mutable reference. See `WeakNode` and `NodeMut` for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `WeakNode` is from a different graph or refers to a node that has been removed.
+    pub fn try_weak_mut(
+        &mut self,
+        node: WeakNode<T, E>,
+    ) -> Result<NodeMut<'_, T, E, K>, GraphError> {
+        let idx = self.resolve(node)?;
+        Ok(NodeMut { graph: self, idx })
     }
 
     /// Returns the shortest path between two nodes, if a path exists and the edges can be manipulated and
@@ -114,81 +346,397 @@ impl<T, E> Graph<T, E> {
     ///
     /// Panics if either the start or end node is not part of this graph.
     #[must_use]
-    pub fn dijkstras(&self, start: Node<'_, T, E>, end: Node<'_, T, E>) -> Option<Path<'_, T, E>>
+    pub fn dijkstras(
+        &self,
+        start: Node<'_, T, E, K>,
+        end: Node<'_, T, E, K>,
+    ) -> Option<Path<'_, T, E, K>>
     where
         E: Default + Clone + Ord + Add<E, Output = E>,
     {
-        assert!(
-            std::ptr::eq(self, start.graph),
-            "Attempt to generate path for node outside of graph"
-        );
-        assert!(
-            std::ptr::eq(self, end.graph),
-            "Attempt to generate path for node outside of graph"
-        );
-        let mut remaining: Vec<_> = (0..self.nodes.len()).collect();
-        let mut distance: Vec<_> = vec![None; self.nodes.len()];
-        distance[start.idx] = Some(E::default());
-        let mut predecessors: Vec<_> = vec![None; self.nodes.len()];
-
-        'outer: while let Some((next_rem, &next)) =
-            remaining.iter().enumerate().min_by(|(_, &a), (_, &b)| {
-                match (&distance[a], &distance[b]) {
-                    (Some(_), None) => Ordering::Less,
-                    (None, None) => Ordering::Equal,
-                    (None, Some(_)) => Ordering::Greater,
-                    (Some(a), Some(b)) => a.cmp(b),
-                }
-            })
-        {
-            remaining.remove(next_rem);
-            for (step, weight) in &self.nodes[next].edges {
-                if next == end.idx {
-                    break 'outer;
-                }
-                let new_weight = distance[next].clone()? + weight.clone();
-                // if the old distance is less than the old one, do nothing.
-                if distance[*step]
-                    .as_ref()
-                    .is_some_and(|dst| dst <= &new_weight)
-                {
-                    continue;
-                }
-                distance[*step] = Some(new_weight);
-                predecessors[*step] = Some(next);
-            }
+        self.try_dijkstras(start, end).unwrap_or_else(|err| {
+            panic!("Attempt to generate path for node outside of graph: {err}")
+        })
+    }
+
+    /// Returns the shortest path between two nodes, if a path exists and the edges can be manipulated and
+    /// compared appropriately.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either the start or end node is not part of this graph.
+    pub fn try_dijkstras(
+        &self,
+        start: Node<'_, T, E, K>,
+        end: Node<'_, T, E, K>,
+    ) -> Result<Option<Path<'_, T, E, K>>, GraphError>
+    where
+        E: Default + Clone + Ord + Add<E, Output = E>,
+    {
+        self.check(start)?;
+        self.check(end)?;
+        Ok(self.shortest_path(start.idx, end.idx))
+    }
+
+    fn shortest_path(&self, start: usize, end: usize) -> Option<Path<'_, T, E, K>>
+    where
+        E: Default + Clone + Ord + Add<E, Output = E>,
+    {
+        search::dijkstra(self.nodes.len(), start, Some(end), |node| {
+            self.weighted_edges(node)
+        })
+        .path(self, start, end)
+    }
+
+    /// Create a graph of any kind with no nodes, for `new` and the wrappers that hold a graph of their own kind
+    fn empty() -> Self {
+        Self {
+            id: NEXT_GRAPH_ID.fetch_add(1, atomic::Ordering::Relaxed),
+            nodes: Vec::new(),
+            vacant: Vec::new(),
+            next_edge: 0,
+            kind: PhantomData,
         }
-        let mut prev = end.idx;
-        let mut path = Vec::new();
-        while prev != start.idx {
-            path.push(prev);
-            prev = predecessors[prev]?;
+    }
+
+    pub fn insert(&mut self, value: T) -> Node<'_, T, E, K> {
+        let adjacency = Adjacency {
+            value,
+            edges: BTreeMap::new(),
+            incoming: BTreeSet::new(),
+        };
+        let slot = Slot {
+            generation: NEXT_GENERATION.fetch_add(1, atomic::Ordering::Relaxed),
+            adjacency: Some(adjacency),
+        };
+        let idx = if let Some(idx) = self.vacant.pop() {
+            self.nodes[idx] = slot;
+            idx
+        } else {
+            self.nodes.push(slot);
+            self.nodes.len() - 1
+        };
+        Node { graph: self, idx }
+    }
+
+    /// Copy the nodes of this graph into a graph with no edges. Like a clone, the copy has an identity of its own
+    /// but can `translate` the `WeakNode`s of this graph.
+    fn without_edges(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            id: NEXT_GRAPH_ID.fetch_add(1, atomic::Ordering::Relaxed),
+            nodes: self
+                .nodes
+                .iter()
+                .map(|slot| Slot {
+                    generation: slot.generation,
+                    adjacency: slot.adjacency.as_ref().map(|adjacency| Adjacency {
+                        value: adjacency.value.clone(),
+                        edges: BTreeMap::new(),
+                        incoming: BTreeSet::new(),
+                    }),
+                })
+                .collect(),
+            vacant: self.vacant.clone(),
+            next_edge: self.next_edge,
+            kind: PhantomData,
         }
-        path.push(prev);
-        path.reverse();
-        Some(Path { graph: self, path })
     }
 
+    /// Find the node of this graph that a `WeakNode` of a copy of it refers to, such as the graph it was cloned from,
+    /// a clone of it or the spanning forest it was built as.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node was not copied between the two graphs or has since been removed from either.
     #[must_use]
-    /// Create a graph with no nodes
-    pub const fn new() -> Self {
-        Self { nodes: Vec::new() }
+    pub fn translate(&self, node: WeakNode<T, E>) -> WeakNode<T, E> {
+        self.try_translate(node)
+            .unwrap_or_else(|err| panic!("Attempt to translate an invalid weak ref: {err}"))
     }
 
-    pub fn insert(&mut self, value: T) -> Node<'_, T, E> {
-        let idx = self.nodes.len();
-        self.nodes.push(Adjacency {
-            value,
-            edges: BTreeMap::new(),
+    /// Find the node of this graph that a `WeakNode` of a copy of it refers to, such as the graph it was cloned from,
+    /// a clone of it or the spanning forest it was built as.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the node was not copied between the two graphs or has since been removed from either.
+    pub fn try_translate(&self, node: WeakNode<T, E>) -> Result<WeakNode<T, E>, GraphError> {
+        if node.graph == self.id {
+            return self.resolve(node).map(|idx| self.handle(idx));
+        }
+        let slot = self.nodes.get(node.idx).ok_or(GraphError::ForeignNode)?;
+        // generations are never reused across graphs, so only a copy of the same node can share one
+        if slot.generation != node.generation {
+            return Err(GraphError::ForeignNode);
+        }
+        if slot.adjacency.is_none() {
+            return Err(GraphError::StaleNode);
+        }
+        Ok(self.handle(node.idx))
+    }
+
+    /// Find the index of the node a `WeakNode` refers to, checking that it belongs to this graph and is still live.
+    fn resolve(&self, node: WeakNode<T, E>) -> Result<usize, GraphError> {
+        if node.graph != self.id {
+            return Err(GraphError::ForeignNode);
+        }
+        let slot = self.nodes.get(node.idx).ok_or(GraphError::OutOfBounds)?;
+        if slot.generation != node.generation || slot.adjacency.is_none() {
+            return Err(GraphError::StaleNode);
+        }
+        Ok(node.idx)
+    }
+
+    /// The nodes the node at `idx` has an edge to
+    fn successors(&self, idx: usize) -> impl Iterator<Item = usize> + '_ {
+        self.edges_from(idx).map(|(next, _, _)| next)
+    }
+
+    /// The nodes with an edge to the node at `idx`
+    fn predecessors(&self, idx: usize) -> impl Iterator<Item = usize> + '_ {
+        let adjacency = self.adjacency(idx);
+        let outgoing = K::UNDIRECTED.then(|| adjacency.edges.keys().map(|&(next, _)| next));
+        adjacency
+            .incoming
+            .iter()
+            .map(|&(prev, _)| prev)
+            .filter(move |&prev| !K::UNDIRECTED || prev != idx)
+            .chain(outgoing.into_iter().flatten())
+    }
+
+    /// The number of edges to and from the node at `idx`, where an undirected edge counts both ways but a loop
+    /// only once
+    fn degree(&self, idx: usize) -> (usize, usize) {
+        let adjacency = self.adjacency(idx);
+        let (incoming, outgoing) = (adjacency.incoming.len(), adjacency.edges.len());
+        if K::UNDIRECTED {
+            let both = incoming + outgoing - adjacency.to(idx).count();
+            (both, both)
+        } else {
+            (incoming, outgoing)
+        }
+    }
+
+    /// The weight of the edge from `start` to `end`, or of the earliest one if there are parallel edges
+    fn edge(&self, start: usize, end: usize) -> Option<&E> {
+        let (start, end, id) = self.edge_id(start, end)?;
+        self.adjacency(start).edges.get(&(end, id))
+    }
+
+    /// The weight of the edge from `start` to `end` with the given id, looking at the other end for an undirected
+    /// edge that was added from `end`
+    fn edge_with_id(&self, start: usize, end: usize, id: usize) -> Option<&E> {
+        self.adjacency(start).edges.get(&(end, id)).or_else(|| {
+            K::UNDIRECTED
+                .then(|| self.adjacency(end).edges.get(&(start, id)))
+                .flatten()
+        })
+    }
+
+    /// The id of the cheapest of the edges from `start` to `end`
+    fn cheapest_edge_id(&self, start: usize, end: usize) -> Option<usize>
+    where
+        E: Ord,
+    {
+        let reverse = (K::UNDIRECTED && start != end).then(|| self.adjacency(end).to(start));
+        self.adjacency(start)
+            .to(end)
+            .chain(reverse.into_iter().flatten())
+            .min_by(|(_, a), (_, b)| a.cmp(b))
+            .map(|(&(_, id), _)| id)
+    }
+
+    /// The ends and id of the earliest edge from `start` to `end`, with the ends in the order the edge is stored,
+    /// which is reversed when an undirected edge was added from `end`
+    fn edge_id(&self, start: usize, end: usize) -> Option<(usize, usize, usize)> {
+        if let Some((&(_, id), _)) = self.adjacency(start).to(end).next() {
+            return Some((start, end, id));
+        }
+        let (&(_, id), _) = K::UNDIRECTED.then(|| self.adjacency(end).to(start).next())??;
+        Some((end, start, id))
+    }
+
+    /// Add an edge from `start` to `end`, keeping the incoming edges of `end` up to date, and return its id. Outside
+    /// of a `MultiGraph` this replaces the weight of any edge already between them.
+    fn add_edge(&mut self, start: usize, end: usize, weight: E) -> usize {
+        let (start, end, id) = if K::MULTI {
+            self.next_edge += 1;
+            (start, end, self.next_edge - 1)
+        } else {
+            self.edge_id(start, end).unwrap_or((start, end, 0))
+        };
+        self.adjacency_mut(start).edges.insert((end, id), weight);
+        self.adjacency_mut(end).incoming.insert((start, id));
+        id
+    }
+
+    /// Remove the edge from `start` to `end` with the given id, as it is stored
+    fn remove_edge(&mut self, start: usize, end: usize, id: usize) -> Option<E> {
+        let weight = self.adjacency_mut(start).edges.remove(&(end, id))?;
+        self.adjacency_mut(end).incoming.remove(&(start, id));
+        Some(weight)
+    }
+
+    /// The neighbors of the node at `idx`, following undirected edges from both ends
+    fn neighbors_of(&self, idx: usize) -> Neighbors<'_, T, E, K> {
+        let adjacency = self.adjacency(idx);
+        Neighbors {
+            graph: self,
+            idx,
+            outgoing: Some(adjacency.edges.keys()),
+            incoming: K::UNDIRECTED.then(|| adjacency.incoming.iter()),
+        }
+    }
+
+    /// The neighbors of every slot when the direction of edges is ignored, without loops. The edges between two
+    /// nodes are paired up with those in the opposite direction, and the other node is listed once for each pair or
+    /// unpaired edge, so parallel edges repeat it.
+    fn undirected_neighbors(&self) -> Vec<Vec<usize>> {
+        let mut neighbors = vec![Vec::new(); self.nodes.len()];
+        for idx in self.indices() {
+            let mut counts: BTreeMap<usize, (usize, usize)> = BTreeMap::new();
+            for next in self.successors(idx).filter(|&next| next != idx) {
+                counts.entry(next).or_default().0 += 1;
+            }
+            for prev in self.predecessors(idx).filter(|&prev| prev != idx) {
+                counts.entry(prev).or_default().1 += 1;
+            }
+            neighbors[idx] = counts
+                .into_iter()
+                .flat_map(|(next, (outgoing, incoming))| {
+                    iter::repeat_n(next, outgoing.max(incoming))
+                })
+                .collect();
+        }
+        neighbors
+    }
+
+    /// The outgoing edges of the node at `idx` by their end and id, including undirected edges stored at their other
+    /// end
+    fn edges_from(&self, idx: usize) -> impl Iterator<Item = (usize, usize, &E)> + '_ {
+        let adjacency = self.adjacency(idx);
+        let incoming = K::UNDIRECTED.then(|| {
+            adjacency
+                .incoming
+                .iter()
+                .filter(move |&&(prev, _)| prev != idx)
+                .filter_map(move |&(prev, id)| {
+                    Some((prev, id, self.adjacency(prev).edges.get(&(idx, id))?))
+                })
         });
-        Node { graph: self, idx }
+        adjacency
+            .edges
+            .iter()
+            .map(|(&(next, id), weight)| (next, id, weight))
+            .chain(incoming.into_iter().flatten())
+    }
+
+    /// The outgoing edges of the node at `idx`, with cloned weights
+    fn weighted_edges(&self, idx: usize) -> impl Iterator<Item = (usize, usize, E)> + '_
+    where
+        E: Clone,
+    {
+        self.edges_from(idx)
+            .map(|(next, id, weight)| (next, id, weight.clone()))
+    }
+
+    /// Check that a `Node` was borrowed from this graph
+    fn check(&self, node: Node<'_, T, E, K>) -> Result<(), GraphError> {
+        if std::ptr::eq(self, node.graph) {
+            Ok(())
+        } else {
+            Err(GraphError::ForeignNode)
+        }
+    }
+
+    /// Create the weak reference for the node at `idx`
+    fn handle(&self, idx: usize) -> WeakNode<T, E> {
+        WeakNode {
+            graph: self.id,
+            idx,
+            generation: self.nodes[idx].generation,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Indices of every node that has not been removed
+    fn indices(&self) -> impl Iterator<Item = usize> + '_ {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, slot)| slot.adjacency.as_ref().map(|_| idx))
+    }
+
+    fn adjacency(&self, idx: usize) -> &Adjacency<T, E> {
+        self.nodes[idx]
+            .adjacency
+            .as_ref()
+            .expect("Attempt to access a Node that has been removed")
+    }
+
+    fn adjacency_mut(&mut self, idx: usize) -> &mut Adjacency<T, E> {
+        self.nodes[idx]
+            .adjacency
+            .as_mut()
+            .expect("Attempt to access a Node that has been removed")
     }
 }
 
+/// A position in the node pool of a `Graph`, which may be vacant after its node is removed
+#[derive(Clone)]
+struct Slot<T, E> {
+    /// Taken from `NEXT_GENERATION` whenever a node is inserted into this slot, so old `WeakNode`s can be recognized
+    /// as stale and copies of a node recognized by `translate`
+    generation: usize,
+    adjacency: Option<Adjacency<T, E>>,
+}
+
 #[derive(Clone)]
 struct Adjacency<T, E = ()> {
     value: T,
-    edges: BTreeMap<usize, E>,
+    /// The edges from this node by their end and id, where the id tells parallel edges of a `MultiGraph` apart and
+    /// is always 0 otherwise
+    edges: BTreeMap<(usize, usize), E>,
+    /// The nodes with an edge to this one along with the id of each edge, kept in step with their `edges`
+    incoming: BTreeSet<(usize, usize)>,
+}
+
+impl<T, E> Adjacency<T, E> {
+    /// The edges from this node to the node at `end`, in the order they were added
+    fn to(&self, end: usize) -> btree_map::Range<'_, (usize, usize), E> {
+        self.edges.range((end, 0)..=(end, usize::MAX))
+    }
+}
+
+impl<T, E, K: Kind> Index<WeakNode<T, E>> for Graph<T, E, K> {
+    type Output = T;
+
+    fn index(&self, node: WeakNode<T, E>) -> &Self::Output {
+        let idx = self
+            .resolve(node)
+            .unwrap_or_else(|err| panic!("Attempt to index a graph with an invalid node: {err}"));
+        &self.adjacency(idx).value
+    }
+}
+
+impl<T, E, K: Kind> IndexMut<WeakNode<T, E>> for Graph<T, E, K> {
+    fn index_mut(&mut self, node: WeakNode<T, E>) -> &mut Self::Output {
+        let idx = self
+            .resolve(node)
+            .unwrap_or_else(|err| panic!("Attempt to index a graph with an invalid node: {err}"));
+        &mut self.adjacency_mut(idx).value
+    }
+}
+
+impl<'a, T, E, K: Kind> IntoIterator for &'a mut Graph<T, E, K> {
+    type IntoIter = IterMut<'a, T, E>;
+    type Item = (WeakNode<T, E>, &'a mut T);
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
 }
 
 impl<T> Graph<T> {
@@ -196,7 +744,7 @@ impl<T> Graph<T> {
     ///
     /// # Panics
     ///
-    /// Panics if either the start or end node refers outside of the pool kept by the graph.
+    /// Panics if either the start or end node is not a live node of this graph.
     pub fn connect(&mut self, start: WeakNode<T>, end: WeakNode<T>) {
         self.connect_weighted(start, end, ());
     }
@@ -205,53 +753,85 @@ impl<T> Graph<T> {
     ///
     /// # Panics
     ///
-    /// Panics if either the start or end node refers outside of the pool kept by the graph.
+    /// Panics if either the start or end node is not a live node of this graph.
     pub fn connect_undirected(&mut self, start: WeakNode<T>, end: WeakNode<T>) {
-        self.connect_weighted(start, end, ());
-        self.connect_weighted(end, start, ());
+        self.connect_undirected_weighted(start, end, ());
     }
 }
 
 /// A reference to a single node within a graph
-pub struct Node<'a, T, E = ()> {
-    graph: &'a Graph<T, E>,
+pub struct Node<'a, T, E = (), K = Directed> {
+    graph: &'a Graph<T, E, K>,
     idx: usize,
 }
 
-impl<'a, T, E> Clone for Node<'a, T, E> {
+impl<T, E, K: Kind> Clone for Node<'_, T, E, K> {
     fn clone(&self) -> Self {
         *self
     }
 }
 
-impl<'a, T, E> Copy for Node<'a, T, E> {}
+impl<T, E, K: Kind> Copy for Node<'_, T, E, K> {}
 
-impl<'a, T, E> Node<'a, T, E> {
+impl<'a, T, E, K: Kind> Node<'a, T, E, K> {
     /// Returns the neighbors of this `Node`.
     #[must_use]
-    pub fn neighbors(&self) -> Neighbors<'a, T, E> {
+    pub fn neighbors(&self) -> Neighbors<'a, T, E, K> {
+        self.graph.neighbors_of(self.idx)
+    }
+
+    /// Returns the edges from this `Node`, along with their weights.
+    #[must_use]
+    pub fn edges(&self) -> Edges<'a, T, E, K> {
+        Edges {
+            graph: self.graph,
+            sources: 0..0,
+            source: self.idx,
+            current: Some(self.graph.adjacency(self.idx).edges.iter()),
+            incoming: K::UNDIRECTED.then(|| self.graph.adjacency(self.idx).incoming.iter()),
+        }
+    }
+
+    /// Returns the nodes with an edge to this `Node`.
+    #[must_use]
+    pub fn predecessors(&self) -> Neighbors<'a, T, E, K> {
+        let adjacency = self.graph.adjacency(self.idx);
         Neighbors {
             graph: self.graph,
-            neighbors: self.graph.nodes[self.idx].edges.iter(),
+            idx: self.idx,
+            outgoing: K::UNDIRECTED.then(|| adjacency.edges.keys()),
+            incoming: Some(adjacency.incoming.iter()),
         }
     }
 
+    /// Returns the number of edges to this `Node`.
+    #[must_use]
+    pub fn in_degree(&self) -> usize {
+        self.graph.degree(self.idx).0
+    }
+
+    /// Returns the number of edges from this `Node`.
+    #[must_use]
+    pub fn out_degree(&self) -> usize {
+        self.graph.degree(self.idx).1
+    }
+
     /// Returns the breadth-first iterator through the graph; starting from this `Node`.
     #[must_use]
-    pub fn breadth_first(&self) -> BreadthFirst<'a, T, E> {
+    pub fn breadth_first(&self) -> BreadthFirst<'a, T, E, K> {
         BreadthFirst {
             queue: vec![*self].into(),
-            visited: BTreeSet::new(),
+            visited: BTreeSet::from([self.idx]),
         }
     }
 
     /// Returns the dept-first iterator through the graph; starting from this `Node`.
     #[must_use]
-    pub fn depth_first(&self) -> DepthFirst<'a, T, E> {
+    pub fn depth_first(&self) -> DepthFirst<'a, T, E, K> {
         DepthFirst {
             graph: self.graph,
             stack: vec![self.idx],
-            visited: BTreeSet::new(),
+            visited: BTreeSet::from([self.idx]),
         }
     }
 
@@ -261,29 +841,42 @@ impl<'a, T, E> Node<'a, T, E> {
     where
         T: Clone,
     {
-        self.graph.nodes[self.idx].value.clone()
+        self.graph.adjacency(self.idx).value.clone()
     }
 
     /// Returns the weak reference of this `Node`.
     #[must_use]
-    pub const fn weak(&self) -> WeakNode<T, E> {
-        WeakNode(self.idx, PhantomData)
+    pub fn weak(&self) -> WeakNode<T, E> {
+        self.graph.handle(self.idx)
     }
 }
 
-impl<'a, T, E> Deref for Node<'a, T, E> {
+impl<T, E, K: Kind> Deref for Node<'_, T, E, K> {
     type Target = T;
     fn deref(&self) -> &Self::Target {
-        &self.graph.nodes[self.idx].value
+        &self.graph.adjacency(self.idx).value
     }
 }
 
 /// A weak reference to a node within a graph
 ///
-/// # Safety
-///
-/// If you use a weak node from a different graph, unexpected behavior may occur.
-pub struct WeakNode<T, E = ()>(usize, PhantomData<(T, E)>);
+/// A `WeakNode` remembers which graph created it and which generation of its slot it refers to, so using it with
+/// a different graph or after its node was removed is detected rather than resolving to some other node.
+pub struct WeakNode<T, E = ()> {
+    graph: usize,
+    idx: usize,
+    generation: usize,
+    _marker: PhantomData<(T, E)>,
+}
+
+impl<T, E> WeakNode<T, E> {
+    const fn key(&self) -> (usize, usize, usize) {
+        (self.graph, self.idx, self.generation)
+    }
+}
+
+/// The weak references to the two ends of an edge
+pub type WeakEdge<T, E = ()> = (WeakNode<T, E>, WeakNode<T, E>);
 
 impl<T, E> Copy for WeakNode<T, E> {}
 impl<T, E> Clone for WeakNode<T, E> {
@@ -292,78 +885,296 @@ impl<T, E> Clone for WeakNode<T, E> {
     }
 }
 
-/// An iterator over the direct neighbors of a node within a graph
-pub struct Neighbors<'a, T, E> {
-    graph: &'a Graph<T, E>,
-    neighbors: btree_map::Iter<'a, usize, E>,
+impl<T, E> PartialEq for WeakNode<T, E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
 }
 
-impl<'a, T, E> Iterator for Neighbors<'a, T, E> {
-    type Item = Node<'a, T, E>;
+impl<T, E> Eq for WeakNode<T, E> {}
+
+impl<T, E> PartialOrd for WeakNode<T, E> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T, E> Ord for WeakNode<T, E> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl<T, E> Hash for WeakNode<T, E> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+impl<T, E> Debug for WeakNode<T, E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WeakNode")
+            .field("graph", &self.graph)
+            .field("idx", &self.idx)
+            .field("generation", &self.generation)
+            .finish()
+    }
+}
+
+/// A weak reference to a single edge, which tells it apart from any parallel edges between the same nodes.
+///
+/// Like a `WeakNode`, it does not borrow the graph, and stops resolving once the edge or either of its ends is
+/// removed.
+pub struct EdgeHandle<T, E = ()> {
+    start: WeakNode<T, E>,
+    end: WeakNode<T, E>,
+    id: usize,
+}
+
+impl<T, E> EdgeHandle<T, E> {
+    /// Returns the node this edge starts from.
+    #[must_use]
+    pub const fn start(&self) -> WeakNode<T, E> {
+        self.start
+    }
+
+    /// Returns the node this edge leads to.
+    #[must_use]
+    pub const fn end(&self) -> WeakNode<T, E> {
+        self.end
+    }
+}
+
+impl<T, E> Copy for EdgeHandle<T, E> {}
+impl<T, E> Clone for EdgeHandle<T, E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, E> PartialEq for EdgeHandle<T, E> {
+    fn eq(&self, other: &Self) -> bool {
+        (self.start, self.end, self.id) == (other.start, other.end, other.id)
+    }
+}
+
+impl<T, E> Eq for EdgeHandle<T, E> {}
+
+impl<T, E> Hash for EdgeHandle<T, E> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (self.start, self.end, self.id).hash(state);
+    }
+}
+
+impl<T, E> Debug for EdgeHandle<T, E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EdgeHandle")
+            .field("start", &self.start)
+            .field("end", &self.end)
+            .field("id", &self.id)
+            .finish()
+    }
+}
+
+/// An iterator over the direct neighbors or predecessors of a node within a graph
+pub struct Neighbors<'a, T, E, K = Directed> {
+    graph: &'a Graph<T, E, K>,
+    idx: usize,
+    outgoing: Option<btree_map::Keys<'a, (usize, usize), E>>,
+    incoming: Option<btree_set::Iter<'a, (usize, usize)>>,
+}
+
+impl<'a, T, E, K: Kind> Iterator for Neighbors<'a, T, E, K> {
+    type Item = Node<'a, T, E, K>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        Some(Node {
-            graph: self.graph,
-            idx: *self.neighbors.next()?.0,
+        let (graph, idx) = (self.graph, self.idx);
+        let next = match self.outgoing.as_mut().and_then(Iterator::next) {
+            Some(&(next, _)) => next,
+            // a loop of an undirected graph is already among the outgoing edges
+            None => {
+                self.incoming
+                    .as_mut()?
+                    .find(|&&(prev, _)| !K::UNDIRECTED || prev != idx)?
+                    .0
+            }
+        };
+        Some(Node { graph, idx: next })
+    }
+}
+
+/// An iterator over the nodes of a graph
+pub struct Nodes<'a, T, E, K = Directed> {
+    graph: &'a Graph<T, E, K>,
+    slots: Range<usize>,
+}
+
+impl<'a, T, E, K: Kind> Iterator for Nodes<'a, T, E, K> {
+    type Item = Node<'a, T, E, K>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let graph = self.graph;
+        self.slots.find_map(|idx| {
+            graph.nodes[idx]
+                .adjacency
+                .as_ref()
+                .map(|_| Node { graph, idx })
         })
     }
 }
 
+/// An iterator over the values of the nodes of a graph, along with their weak references
+pub struct IterMut<'a, T, E> {
+    graph: usize,
+    slots: iter::Enumerate<slice::IterMut<'a, Slot<T, E>>>,
+}
+
+impl<'a, T, E> Iterator for IterMut<'a, T, E> {
+    type Item = (WeakNode<T, E>, &'a mut T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let graph = self.graph;
+        self.slots.find_map(|(idx, slot)| {
+            let node = WeakNode {
+                graph,
+                idx,
+                generation: slot.generation,
+                _marker: PhantomData,
+            };
+            Some((node, &mut slot.adjacency.as_mut()?.value))
+        })
+    }
+}
+
+/// An edge within a graph, along with its weight
+pub struct EdgeRef<'a, T, E, K = Directed> {
+    pub source: Node<'a, T, E, K>,
+    pub target: Node<'a, T, E, K>,
+    pub weight: &'a E,
+    id: usize,
+}
+
+impl<T, E, K: Kind> EdgeRef<'_, T, E, K> {
+    /// Converts this `EdgeRef` to a handle that tells it apart from any parallel edges.
+    #[must_use]
+    pub fn weak(&self) -> EdgeHandle<T, E> {
+        EdgeHandle {
+            start: self.source.weak(),
+            end: self.target.weak(),
+            id: self.id,
+        }
+    }
+}
+
+impl<T, E, K: Kind> Clone for EdgeRef<'_, T, E, K> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, E, K: Kind> Copy for EdgeRef<'_, T, E, K> {}
+
+/// An iterator over the edges of a node, or of a whole graph
+pub struct Edges<'a, T, E, K = Directed> {
+    graph: &'a Graph<T, E, K>,
+    /// Slots whose edges are still to be visited after those of `source`
+    sources: Range<usize>,
+    source: usize,
+    current: Option<btree_map::Iter<'a, (usize, usize), E>>,
+    /// The edges of an undirected graph stored at their other end, when iterating the edges of a single node
+    incoming: Option<btree_set::Iter<'a, (usize, usize)>>,
+}
+
+impl<'a, T, E, K: Kind> Iterator for Edges<'a, T, E, K> {
+    type Item = EdgeRef<'a, T, E, K>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (graph, source) = (self.graph, self.source);
+            if let Some((&(target, id), weight)) = self.current.as_mut().and_then(Iterator::next) {
+                return Some(EdgeRef {
+                    source: Node { graph, idx: source },
+                    target: Node { graph, idx: target },
+                    weight,
+                    id,
+                });
+            }
+            let incoming = self.incoming.as_mut().and_then(|incoming| {
+                incoming
+                    .filter(|&&(prev, _)| prev != source)
+                    .find_map(|&(prev, id)| {
+                        Some((prev, id, graph.adjacency(prev).edges.get(&(source, id))?))
+                    })
+            });
+            if let Some((target, id, weight)) = incoming {
+                return Some(EdgeRef {
+                    source: Node { graph, idx: source },
+                    target: Node { graph, idx: target },
+                    weight,
+                    id,
+                });
+            }
+            let source = self.sources.next()?;
+            if let Some(adjacency) = &self.graph.nodes[source].adjacency {
+                self.source = source;
+                self.current = Some(adjacency.edges.iter());
+            }
+        }
+    }
+}
+
 /// A mutable reference to a node within a graph
-pub struct NodeMut<'a, T, E = ()> {
-    graph: &'a mut Graph<T, E>,
+pub struct NodeMut<'a, T, E = (), K = Directed> {
+    graph: &'a mut Graph<T, E, K>,
     idx: usize,
 }
 
-impl<'a, T, E> NodeMut<'a, T, E> {
+impl<'a, T, E, K: Kind> NodeMut<'a, T, E, K> {
     /// Returns the neighbors of this `NodeMut`.
     #[must_use]
-    pub fn neighbors(&'a self) -> Neighbors<'a, T, E> {
-        Neighbors {
-            graph: self.graph,
-            neighbors: self.graph.nodes[self.idx].edges.iter(),
-        }
+    pub fn neighbors(&'a self) -> Neighbors<'a, T, E, K> {
+        self.graph.neighbors_of(self.idx)
     }
 
     /// Converts this `NodeMut` to a weak reference, allowing the corresponding `Graph` to be used elsewhere.
     #[must_use]
-    pub const fn weak(&self) -> WeakNode<T, E> {
-        WeakNode(self.idx, PhantomData)
+    pub fn weak(&self) -> WeakNode<T, E> {
+        self.graph.handle(self.idx)
     }
 }
 
-impl<'a, T, E> Deref for NodeMut<'a, T, E> {
+impl<T, E, K: Kind> Deref for NodeMut<'_, T, E, K> {
     type Target = T;
 
     fn deref(&self) -> &Self::Target {
-        &self.graph.nodes[self.idx].value
+        &self.graph.adjacency(self.idx).value
     }
 }
 
-impl<'a, T, E> DerefMut for NodeMut<'a, T, E> {
+impl<T, E, K: Kind> DerefMut for NodeMut<'_, T, E, K> {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.graph.nodes[self.idx].value
+        &mut self.graph.adjacency_mut(self.idx).value
     }
 }
 
 /// An iterator over a `Graph`, returning `Node`s in depth-first order
-pub struct DepthFirst<'a, T, E> {
-    graph: &'a Graph<T, E>,
+pub struct DepthFirst<'a, T, E, K = Directed> {
+    graph: &'a Graph<T, E, K>,
     stack: Vec<usize>,
     visited: BTreeSet<usize>,
 }
 
-impl<'a, T, E> Iterator for DepthFirst<'a, T, E> {
-    type Item = Node<'a, T, E>;
+impl<'a, T, E, K: Kind> Iterator for DepthFirst<'a, T, E, K> {
+    type Item = Node<'a, T, E, K>;
 
     fn next(&mut self) -> Option<Self::Item> {
         let idx = self.stack.pop()?;
-        for end in self.graph.nodes[idx].edges.keys() {
-            if self.visited.contains(end) {
+        for end in self.graph.successors(idx) {
+            if self.visited.contains(&end) {
                 continue;
             }
-            self.stack.push(*end);
-            self.visited.insert(*end);
+            self.stack.push(end);
+            self.visited.insert(end);
         }
         Some(Node {
             graph: self.graph,
@@ -373,13 +1184,13 @@ impl<'a, T, E> Iterator for DepthFirst<'a, T, E> {
 }
 
 /// An iterator over a `Graph`, returning `Node`s in breadth-first order
-pub struct BreadthFirst<'a, T, E> {
-    queue: VecDeque<Node<'a, T, E>>,
+pub struct BreadthFirst<'a, T, E, K = Directed> {
+    queue: VecDeque<Node<'a, T, E, K>>,
     visited: BTreeSet<usize>,
 }
 
-impl<'a, T, E> Iterator for BreadthFirst<'a, T, E> {
-    type Item = Node<'a, T, E>;
+impl<'a, T, E, K: Kind> Iterator for BreadthFirst<'a, T, E, K> {
+    type Item = Node<'a, T, E, K>;
     fn next(&mut self) -> Option<Self::Item> {
         let next = self.queue.pop_front()?;
         for n in next.neighbors() {
@@ -394,48 +1205,75 @@ impl<'a, T, E> Iterator for BreadthFirst<'a, T, E> {
 }
 
 /// A path through a `Graph`
-pub struct Path<'a, T, E> {
-    graph: &'a Graph<T, E>,
-    path: Vec<usize>,
+pub struct Path<'a, T, E, K = Directed> {
+    graph: &'a Graph<T, E, K>,
+    nodes: Vec<usize>,
+    /// The id of the edge taken from each node of `nodes` to the next
+    edges: Vec<usize>,
 }
 
-impl<'a, T, E> Path<'a, T, E> {
+impl<'a, T, E, K: Kind> Path<'a, T, E, K> {
     /// Returns an iterator over the `Node`s that make up this `Path`
     #[must_use]
-    pub fn iter(&'a self) -> PathIterator<'a, T, E> {
+    pub fn iter(&'a self) -> PathIterator<'a, T, E, K> {
         PathIterator {
             graph: self.graph,
-            iter: self.path.iter(),
+            iter: self.nodes.iter(),
         }
     }
 
-    /// Add the provided `WeakNode` to the end of this `Path`
+    /// Add the provided `WeakNode` to the end of this `Path`, taking the earliest of any parallel edges to it
     ///
     /// # Panics
     ///
-    /// Panics if the provided `WeakNode` would index outside of the pool used by the `Graph`
+    /// Panics if the provided `WeakNode` is not a live node of the `Graph`, or if there is no edge to it from the
+    /// current end of this `Path`
     pub fn push(&mut self, node: WeakNode<T, E>) {
-        assert!(
-            node.0 < self.graph.nodes.len(),
-            "Attempt to access Node outside of the Graph"
-        );
-        self.path.push(node.0);
+        self.try_push(node)
+            .unwrap_or_else(|err| panic!("Attempt to extend Path with an invalid Node: {err}"));
+    }
+
+    /// Add the provided `WeakNode` to the end of this `Path`, taking the earliest of any parallel edges to it
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the provided `WeakNode` is not a live node of the `Graph`, or if there is no edge to it
+    /// from the current end of this `Path`
+    pub fn try_push(&mut self, node: WeakNode<T, E>) -> Result<(), GraphError> {
+        let idx = self.graph.resolve(node)?;
+        if let Some(&last) = self.nodes.last() {
+            let (_, _, id) = self
+                .graph
+                .edge_id(last, idx)
+                .ok_or(GraphError::MissingEdge)?;
+            self.edges.push(id);
+        }
+        self.nodes.push(idx);
+        Ok(())
     }
 }
 
-impl<'a, T, E: Default + Clone + AddAssign<E>> Path<'a, T, E> {
+impl<T, E: Default + Clone + AddAssign<E>, K: Kind> Path<'_, T, E, K> {
     #[must_use]
-    /// Calculate the length of the path
+    /// Calculate the length of the path, adding up the weights of the edges it takes
+    ///
+    /// # Panics
+    ///
+    /// Panics if the path takes an edge that is not part of the graph, which can't happen while the path borrows it.
     pub fn len(&self) -> E {
         let mut len = E::default();
-        for i in 0..(self.path.len() - 1) {
-            len += self.graph.nodes[self.path[i]].edges[&self.path[i + 1]].clone();
+        for (pair, &id) in self.nodes.windows(2).zip(&self.edges) {
+            len += self
+                .graph
+                .edge_with_id(pair[0], pair[1], id)
+                .expect("Path takes an edge that is not part of the graph")
+                .clone();
         }
         len
     }
 }
 
-impl<'a, T: Debug, E> Debug for Path<'a, T, E> {
+impl<T: Debug, E, K: Kind> Debug for Path<'_, T, E, K> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut ls = f.debug_list();
         for value in self {
@@ -445,22 +1283,22 @@ impl<'a, T: Debug, E> Debug for Path<'a, T, E> {
     }
 }
 
-impl<'a, T, E> IntoIterator for &'a Path<'a, T, E> {
-    type IntoIter = PathIterator<'a, T, E>;
-    type Item = Node<'a, T, E>;
+impl<'a, T, E, K: Kind> IntoIterator for &'a Path<'a, T, E, K> {
+    type IntoIter = PathIterator<'a, T, E, K>;
+    type Item = Node<'a, T, E, K>;
     fn into_iter(self) -> Self::IntoIter {
         self.iter()
     }
 }
 
 /// An iterator over `Node`s in a `Path`
-pub struct PathIterator<'a, T, E> {
-    graph: &'a Graph<T, E>,
+pub struct PathIterator<'a, T, E, K = Directed> {
+    graph: &'a Graph<T, E, K>,
     iter: slice::Iter<'a, usize>,
 }
 
-impl<'a, T, E> Iterator for PathIterator<'a, T, E> {
-    type Item = Node<'a, T, E>;
+impl<'a, T, E, K: Kind> Iterator for PathIterator<'a, T, E, K> {
+    type Item = Node<'a, T, E, K>;
 
     fn next(&mut self) -> Option<Self::Item> {
         Some(Node {
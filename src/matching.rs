@@ -0,0 +1,427 @@
+use std::{
+    collections::{BTreeMap, VecDeque},
+    error::Error,
+    fmt::{Debug, Display},
+    ops::{Add, Neg, Sub},
+};
+
+use crate::{Directed, Graph, GraphError, Kind, Node, WeakEdge, WeakNode};
+
+impl<T, E, K: Kind> Graph<T, E, K> {
+    /// Split the nodes into two sides so that every edge joins one side to the other, ignoring the direction of
+    /// edges.
+    ///
+    /// # Errors
+    ///
+    /// Returns a cycle with an odd number of edges if there is no such split.
+    #[allow(clippy::type_complexity)]
+    pub fn is_bipartite(
+        &self,
+    ) -> Result<(Vec<WeakNode<T, E>>, Vec<WeakNode<T, E>>), OddCycle<'_, T, E, K>> {
+        let side = self.two_coloring()?;
+        let (left, right): (Vec<usize>, Vec<usize>) = self.indices().partition(|&idx| !side[idx]);
+        Ok((
+            left.into_iter().map(|idx| self.handle(idx)).collect(),
+            right.into_iter().map(|idx| self.handle(idx)).collect(),
+        ))
+    }
+
+    /// Find a maximum matching of a bipartite graph with the Hopcroft-Karp algorithm, in O(E √V) time.
+    ///
+    /// Each pair has its first node from the first side returned by `is_bipartite`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a cycle with an odd number of edges if the graph is not bipartite.
+    pub fn maximum_bipartite_matching(&self) -> Result<Vec<WeakEdge<T, E>>, OddCycle<'_, T, E, K>> {
+        let side = self.two_coloring()?;
+        let neighbors = self.undirected_neighbors();
+        let left: Vec<usize> = self.indices().filter(|&idx| !side[idx]).collect();
+        let mut mate: Vec<Option<usize>> = vec![None; self.nodes.len()];
+
+        loop {
+            // layer the left side by the length of the shortest alternating path from an unmatched node
+            let mut layer: Vec<Option<usize>> = vec![None; self.nodes.len()];
+            let mut queue: VecDeque<usize> = left
+                .iter()
+                .copied()
+                .filter(|&l| mate[l].is_none())
+                .collect();
+            for &l in &queue {
+                layer[l] = Some(0);
+            }
+            let mut found = false;
+            while let Some(l) = queue.pop_front() {
+                for &r in &neighbors[l] {
+                    match mate[r] {
+                        None => found = true,
+                        Some(next) if layer[next].is_none() => {
+                            layer[next] = layer[l].map(|layer| layer + 1);
+                            queue.push_back(next);
+                        }
+                        Some(_) => {}
+                    }
+                }
+            }
+            if !found {
+                break;
+            }
+
+            // augment along vertex-disjoint shortest alternating paths, with a depth first search from each
+            // unmatched node that only steps one layer deeper
+            let mut next_neighbor = vec![0; self.nodes.len()];
+            for &start in &left {
+                if mate[start].is_some() {
+                    continue;
+                }
+                let mut lefts = vec![start];
+                let mut rights = Vec::new();
+                while let Some(&l) = lefts.last() {
+                    let Some(&r) = neighbors[l].get(next_neighbor[l]) else {
+                        // nothing more can be reached from here in this phase
+                        layer[l] = None;
+                        lefts.pop();
+                        rights.pop();
+                        continue;
+                    };
+                    next_neighbor[l] += 1;
+                    match mate[r] {
+                        None => {
+                            rights.push(r);
+                            for (&l, &r) in lefts.iter().zip(&rights) {
+                                mate[l] = Some(r);
+                                mate[r] = Some(l);
+                            }
+                            break;
+                        }
+                        Some(next) if layer[next] == layer[l].map(|layer| layer + 1) => {
+                            rights.push(r);
+                            lefts.push(next);
+                        }
+                        Some(_) => {}
+                    }
+                }
+            }
+        }
+
+        Ok(left
+            .into_iter()
+            .filter_map(|l| Some((self.handle(l), self.handle(mate[l]?))))
+            .collect())
+    }
+
+    /// Pair every node of `left` with a node of `right` so that the total weight of the edges between pairs is as
+    /// small as possible, using the Hungarian algorithm in O(n³) time.
+    ///
+    /// The weight of a pair is that of the edge from the left node to the right one, or of the edge the other way
+    /// if there is none, and nodes without an edge between them can't be paired. The algorithm keeps potentials
+    /// that can go below zero, so `E` has to be a signed type, as the `Neg` bound ensures.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the two sides have different sizes, if any node is not a live node of this graph, or if
+    /// the edges don't allow every node to be paired.
+    pub fn assignment(
+        &self,
+        left: &[WeakNode<T, E>],
+        right: &[WeakNode<T, E>],
+    ) -> Result<Assignment<T, E>, AssignmentError>
+    where
+        E: Default + Clone + Ord + Add<E, Output = E> + Sub<E, Output = E> + Neg<Output = E>,
+    {
+        if left.len() != right.len() {
+            return Err(AssignmentError::Unbalanced {
+                left: left.len(),
+                right: right.len(),
+            });
+        }
+        let n = left.len();
+        let left: Vec<usize> = left
+            .iter()
+            .map(|&node| self.resolve(node))
+            .collect::<Result<_, _>>()?;
+        let right: Vec<usize> = right
+            .iter()
+            .map(|&node| self.resolve(node))
+            .collect::<Result<_, _>>()?;
+        let row: BTreeMap<usize, usize> = left
+            .iter()
+            .enumerate()
+            .map(|(i, &idx)| (idx, i + 1))
+            .collect();
+        let column: BTreeMap<usize, usize> = right
+            .iter()
+            .enumerate()
+            .map(|(j, &idx)| (idx, j + 1))
+            .collect();
+
+        // the cost matrix is indexed from one, leaving row and column zero for the algorithm's bookkeeping
+        let mut cost: Vec<Vec<Option<&E>>> = vec![vec![None; n + 1]; n + 1];
+        for (&idx, &i) in &row {
+            for (next, _, weight) in self.edges_from(idx) {
+                if let Some(&j) = column.get(&next) {
+                    cost[i][j] = Some(weight);
+                }
+            }
+        }
+        for (&idx, &j) in &column {
+            for (next, _, weight) in self.edges_from(idx) {
+                if let Some(&i) = row.get(&next) {
+                    cost[i][j].get_or_insert(weight);
+                }
+            }
+        }
+
+        let matched = hungarian(&cost).ok_or(AssignmentError::NoPerfectMatching)?;
+        let mut total = E::default();
+        let mut partner = vec![0; n];
+        for j in 1..=n {
+            let i = matched[j];
+            total = total + cost[i][j].cloned().unwrap_or_default();
+            partner[i - 1] = j - 1;
+        }
+        Ok(Assignment {
+            pairs: (0..n)
+                .map(|i| (self.handle(left[i]), self.handle(right[partner[i]])))
+                .collect(),
+            cost: total,
+        })
+    }
+
+    /// Assign every slot to a side with breadth first search, so that no edge joins two nodes on the same side
+    fn two_coloring(&self) -> Result<Vec<bool>, OddCycle<'_, T, E, K>> {
+        if let Some(idx) = self
+            .indices()
+            .find(|&idx| self.successors(idx).any(|next| next == idx))
+        {
+            return Err(OddCycle {
+                graph: self,
+                nodes: vec![idx, idx],
+            });
+        }
+        let neighbors = self.undirected_neighbors();
+        let mut side = vec![None; self.nodes.len()];
+        let mut parent = vec![None; self.nodes.len()];
+        for root in self.indices() {
+            if side[root].is_some() {
+                continue;
+            }
+            side[root] = Some(false);
+            let mut queue = VecDeque::from([root]);
+            while let Some(node) = queue.pop_front() {
+                for &next in &neighbors[node] {
+                    match side[next] {
+                        None => {
+                            side[next] = side[node].map(|side| !side);
+                            parent[next] = Some(node);
+                            queue.push_back(next);
+                        }
+                        Some(next_side) if Some(next_side) == side[node] => {
+                            return Err(OddCycle {
+                                graph: self,
+                                nodes: odd_cycle(&parent, node, next),
+                            });
+                        }
+                        Some(_) => {}
+                    }
+                }
+            }
+        }
+        Ok(side.into_iter().map(Option::unwrap_or_default).collect())
+    }
+}
+
+/// Close the cycle formed by an edge between two nodes at the same depth parity of a breadth first search tree
+fn odd_cycle(parent: &[Option<usize>], a: usize, b: usize) -> Vec<usize> {
+    let ancestors = |mut node: usize| {
+        let mut path = vec![node];
+        while let Some(prev) = parent[node] {
+            path.push(prev);
+            node = prev;
+        }
+        path
+    };
+    let (mut from_a, mut from_b) = (ancestors(a), ancestors(b));
+    // drop the shared part above the lowest common ancestor, keeping the ancestor itself on one side
+    while from_a.len() > 1
+        && from_b.len() > 1
+        && from_a[from_a.len() - 2] == from_b[from_b.len() - 2]
+    {
+        from_a.pop();
+        from_b.pop();
+    }
+    from_b.pop();
+    let top = *from_a.last().unwrap_or(&a);
+    from_a.reverse();
+    from_a.extend(from_b);
+    from_a.push(top);
+    from_a
+}
+
+/// The Hungarian algorithm on a square cost matrix indexed from one, where `None` forbids a pairing.
+///
+/// Returns the row matched to each column, or `None` if no pairing covers every row.
+fn hungarian<E>(cost: &[Vec<Option<&E>>]) -> Option<Vec<usize>>
+where
+    E: Default + Clone + Ord + Add<E, Output = E> + Sub<E, Output = E> + Neg<Output = E>,
+{
+    let n = cost.len() - 1;
+    let zero = E::default;
+    let mut row_potential = vec![zero(); n + 1];
+    let mut column_potential = vec![zero(); n + 1];
+    // the row matched to each column, with zero meaning unmatched
+    let mut matched = vec![0; n + 1];
+    let mut way = vec![0; n + 1];
+    for row in 1..=n {
+        matched[0] = row;
+        let mut current = 0;
+        let mut slack: Vec<Option<E>> = vec![None; n + 1];
+        let mut used = vec![false; n + 1];
+        // grow a tree of tight edges from the new row until it reaches an unmatched column
+        loop {
+            used[current] = true;
+            let from = matched[current];
+            let mut delta: Option<(E, usize)> = None;
+            for j in 1..=n {
+                if used[j] {
+                    continue;
+                }
+                if let Some(weight) = cost[from][j] {
+                    let reduced =
+                        weight.clone() - row_potential[from].clone() - column_potential[j].clone();
+                    if slack[j].as_ref().is_none_or(|slack| &reduced < slack) {
+                        slack[j] = Some(reduced);
+                        way[j] = current;
+                    }
+                }
+                if let Some(slack) = &slack[j] {
+                    if delta.as_ref().is_none_or(|(delta, _)| slack < delta) {
+                        delta = Some((slack.clone(), j));
+                    }
+                }
+            }
+            // no column can be reached, so this row can't be matched
+            let (delta, next) = delta?;
+            for j in 0..=n {
+                if used[j] {
+                    row_potential[matched[j]] = row_potential[matched[j]].clone() + delta.clone();
+                    column_potential[j] = column_potential[j].clone() - delta.clone();
+                } else if let Some(slack) = &mut slack[j] {
+                    *slack = slack.clone() - delta.clone();
+                }
+            }
+            current = next;
+            if matched[current] == 0 {
+                break;
+            }
+        }
+        // flip the matching along the path back to the new row
+        while current != 0 {
+            let prev = way[current];
+            matched[current] = matched[prev];
+            current = prev;
+        }
+    }
+    Some(matched)
+}
+
+/// A cycle with an odd number of edges, which prevents a graph from being split into two sides.
+///
+/// The cycle ignores the direction of edges, so unlike a `Path` it may step from a node to one that only has an edge
+/// back to it.
+pub struct OddCycle<'a, T, E, K = Directed> {
+    graph: &'a Graph<T, E, K>,
+    nodes: Vec<usize>,
+}
+
+impl<'a, T, E, K: Kind> OddCycle<'a, T, E, K> {
+    /// Returns the nodes around the cycle, starting and ending at the same node
+    #[must_use]
+    pub fn nodes(&self) -> Vec<Node<'a, T, E, K>> {
+        self.nodes
+            .iter()
+            .map(|&idx| Node {
+                graph: self.graph,
+                idx,
+            })
+            .collect()
+    }
+}
+
+impl<T: Debug, E, K: Kind> Debug for OddCycle<'_, T, E, K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("OddCycle")
+            .field(&self.nodes().iter().map(|node| &**node).collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl<T: Debug, E, K: Kind> Display for OddCycle<'_, T, E, K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "graph contains an odd cycle: {:?}",
+            self.nodes().iter().map(|node| &**node).collect::<Vec<_>>()
+        )
+    }
+}
+
+impl<T: Debug, E, K: Kind> Error for OddCycle<'_, T, E, K> {}
+
+/// A minimum cost pairing between two sets of nodes
+pub struct Assignment<T, E> {
+    pairs: Vec<WeakEdge<T, E>>,
+    cost: E,
+}
+
+impl<T, E> Assignment<T, E> {
+    /// Returns the pairs, each with its left node first, in the order of the left nodes
+    #[must_use]
+    pub fn pairs(&self) -> &[WeakEdge<T, E>] {
+        &self.pairs
+    }
+
+    /// Returns the total weight of the edges between pairs
+    #[must_use]
+    pub const fn cost(&self) -> &E {
+        &self.cost
+    }
+}
+
+/// An error from `Graph::assignment`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AssignmentError {
+    /// The two sides have different numbers of nodes
+    Unbalanced { left: usize, right: usize },
+    /// One of the nodes is not a live node of the graph
+    Node(GraphError),
+    /// The edges between the two sides don't allow every node to be paired
+    NoPerfectMatching,
+}
+
+impl From<GraphError> for AssignmentError {
+    fn from(value: GraphError) -> Self {
+        Self::Node(value)
+    }
+}
+
+impl Display for AssignmentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unbalanced { left, right } => {
+                write!(f, "can't pair {left} nodes with {right} nodes")
+            }
+            Self::Node(err) => write!(f, "{err}"),
+            Self::NoPerfectMatching => write!(f, "no assignment pairs every node"),
+        }
+    }
+}
+
+impl Error for AssignmentError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Node(err) => Some(err),
+            _ => None,
+        }
+    }
+}
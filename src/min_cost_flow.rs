@@ -0,0 +1,242 @@
+use std::{
+    collections::BTreeMap,
+    ops::{Add, Mul, Sub},
+};
+
+use crate::{
+    flow::Residual, search, Directed, EdgeHandle, Graph, Kind, NegativeCycle, Node, Path, WeakNode,
+};
+
+/// An edge weight that gives both the capacity of an edge and the cost of each unit of flow along it
+pub trait CostEdge {
+    type Amount;
+
+    /// Returns the most flow the edge can carry
+    fn capacity(&self) -> Self::Amount;
+
+    /// Returns the cost of sending one unit of flow along the edge
+    fn cost(&self) -> Self::Amount;
+}
+
+/// A `(capacity, cost)` pair
+impl<A: Clone> CostEdge for (A, A) {
+    type Amount = A;
+
+    fn capacity(&self) -> A {
+        self.0.clone()
+    }
+
+    fn cost(&self) -> A {
+        self.1.clone()
+    }
+}
+
+impl<T, E: CostEdge, K: Kind> Graph<T, E, K> {
+    /// Send up to `demand` units of flow from `source` to `sink` as cheaply as possible.
+    ///
+    /// This uses successive shortest paths: each augmenting path is found with Dijkstra's algorithm on costs
+    /// reduced by node potentials, which keeps them nonnegative. Negative costs are allowed as long as they don't
+    /// form a cycle, and an unsigned amount works as well since no cost is ever negated. If the network can't carry
+    /// the full demand, the result is the cheapest maximum flow, and its `value` is less than `demand`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `NegativeCycle` if the source can reach a cycle of edges whose costs add up to a negative number.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either the source or sink node is not part of this graph.
+    pub fn min_cost_flow(
+        &self,
+        source: Node<'_, T, E, K>,
+        sink: Node<'_, T, E, K>,
+        demand: E::Amount,
+    ) -> Result<MinCostFlow<'_, T, E, K>, NegativeCycle<'_, T, E, K>>
+    where
+        E::Amount: Default
+            + Clone
+            + Ord
+            + Add<E::Amount, Output = E::Amount>
+            + Sub<E::Amount, Output = E::Amount>
+            + Mul<E::Amount, Output = E::Amount>,
+    {
+        for node in [source, sink] {
+            self.check(node).unwrap_or_else(|err| {
+                panic!("Attempt to find flow for node outside of graph: {err}")
+            });
+        }
+        let zero = E::Amount::default;
+        let mut residual = Residual {
+            to: Vec::new(),
+            capacity: Vec::new(),
+            outgoing: vec![Vec::new(); self.nodes.len()],
+        };
+        // the cost of each edge, which arcs `2 * i` and `2 * i + 1` of the residual network share with opposite signs
+        let mut cost = Vec::new();
+        let mut edges = Vec::new();
+        for idx in self.indices() {
+            for (next, id, weight) in self.edges_from(idx) {
+                if next != idx {
+                    edges.push((idx, next, id, residual.push(idx, next, weight.capacity())));
+                    cost.push(weight.cost());
+                }
+            }
+        }
+
+        // start the potentials at the cheapest cost to each node, which makes every reduced cost nonnegative
+        let nodes: Vec<usize> = self.indices().collect();
+        let mut potential = vec![None; self.nodes.len()];
+        potential[source.idx] = Some(zero());
+        if let Some((path, arcs)) = search::bellman_ford(
+            &nodes,
+            &mut potential,
+            &mut vec![None; self.nodes.len()],
+            |node| {
+                let (residual, cost) = (&residual, &cost);
+                residual.outgoing[node]
+                    .iter()
+                    .filter(move |&&arc| residual.capacity[arc] > zero())
+                    .map(move |&arc| (residual.to[arc], arc, cost[arc / 2].clone()))
+            },
+        ) {
+            // only forward arcs have capacity before any flow is sent, and each edge pushed a pair of arcs
+            return Err(NegativeCycle::new(Path {
+                graph: self,
+                nodes: path,
+                edges: arcs.into_iter().map(|arc| edges[arc / 2].2).collect(),
+            }));
+        }
+        let mut potential: Vec<_> = potential
+            .into_iter()
+            .map(Option::unwrap_or_default)
+            .collect();
+
+        let mut remaining = demand;
+        let mut value = zero();
+        let mut total_cost = zero();
+        while remaining > zero() && source.idx != sink.idx {
+            // reduced costs are never negative, so subtracting the cost of a reverse arc last keeps an unsigned amount
+            // from dropping below zero on the way
+            let reduced_cost = |arc: usize| {
+                let (from, to) = (
+                    &potential[residual.to[arc ^ 1]],
+                    &potential[residual.to[arc]],
+                );
+                if arc & 1 == 0 {
+                    cost[arc / 2].clone() + from.clone() - to.clone()
+                } else {
+                    from.clone() - to.clone() - cost[arc / 2].clone()
+                }
+            };
+            let search = search::dijkstra(self.nodes.len(), source.idx, None, |node| {
+                let residual = &residual;
+                let reduced_cost = &reduced_cost;
+                residual.outgoing[node]
+                    .iter()
+                    .filter(move |&&arc| residual.capacity[arc] > zero())
+                    .map(move |&arc| (residual.to[arc], arc, reduced_cost(arc)))
+            });
+            if search.distance[sink.idx].is_none() {
+                break;
+            }
+
+            let mut path = Vec::new();
+            let mut node = sink.idx;
+            while let Some((prev, arc)) = search.predecessor[node] {
+                path.push(arc);
+                node = prev;
+            }
+            let bottleneck = residual.bottleneck(&path).unwrap_or_default();
+            let amount = bottleneck.min(remaining.clone());
+            let path_cost = path_cost(&cost, &path);
+            residual.augment(&path, &amount);
+            total_cost = total_cost + path_cost * amount.clone();
+            remaining = remaining - amount.clone();
+            value = value + amount;
+
+            for (idx, distance) in search.distance.into_iter().enumerate() {
+                if let Some(distance) = distance {
+                    potential[idx] = potential[idx].clone() + distance;
+                }
+            }
+        }
+
+        Ok(MinCostFlow {
+            graph: self,
+            value,
+            cost: total_cost,
+            flow: edges
+                .into_iter()
+                .map(|(start, end, id, arc)| ((start, end, id), residual.capacity[arc ^ 1].clone()))
+                .collect(),
+        })
+    }
+}
+
+/// The cost of sending one unit of flow along each of the arcs, where a reverse arc gives back the cost of its edge
+fn path_cost<A>(cost: &[A], arcs: &[usize]) -> A
+where
+    A: Default + Clone + Add<A, Output = A> + Sub<A, Output = A>,
+{
+    let (forward, backward) =
+        arcs.iter()
+            .fold((A::default(), A::default()), |(forward, backward), &arc| {
+                if arc & 1 == 0 {
+                    (forward + cost[arc / 2].clone(), backward)
+                } else {
+                    (forward, backward + cost[arc / 2].clone())
+                }
+            });
+    // the path is a shortest one, so it never costs less than nothing even when it undoes some flow
+    forward - backward
+}
+
+/// The cheapest flow through a `Graph` whose edge weights give capacities and costs
+pub struct MinCostFlow<'a, T, E: CostEdge, K = Directed> {
+    graph: &'a Graph<T, E, K>,
+    value: E::Amount,
+    cost: E::Amount,
+    /// The flow along every edge, by its start, end and id
+    flow: BTreeMap<(usize, usize, usize), E::Amount>,
+}
+
+impl<T, E: CostEdge, K: Kind> MinCostFlow<'_, T, E, K> {
+    /// Returns the total amount of flow from the source to the sink
+    #[must_use]
+    pub const fn value(&self) -> &E::Amount {
+        &self.value
+    }
+
+    /// Returns the total cost of the flow
+    #[must_use]
+    pub const fn cost(&self) -> &E::Amount {
+        &self.cost
+    }
+
+    /// Returns the flow along the edge from `start` to `end`, or `None` if there is no such edge. If there are
+    /// parallel edges, this is the flow along the earliest of them, and `flow_on` gives the flow along each.
+    #[must_use]
+    pub fn flow(&self, start: WeakNode<T, E>, end: WeakNode<T, E>) -> Option<&E::Amount> {
+        let start = self.graph.resolve(start).ok()?;
+        let end = self.graph.resolve(end).ok()?;
+        self.flow
+            .range((start, end, 0)..=(start, end, usize::MAX))
+            .next()
+            .map(|(_, flow)| flow)
+    }
+
+    /// Returns the flow along a single edge, or `None` if it is not an edge of the graph.
+    #[must_use]
+    pub fn flow_on(&self, edge: EdgeHandle<T, E>) -> Option<&E::Amount> {
+        let start = self.graph.resolve(edge.start).ok()?;
+        let end = self.graph.resolve(edge.end).ok()?;
+        self.flow.get(&(start, end, edge.id))
+    }
+
+    /// Returns an iterator over every edge that can carry flow along with the flow it carries
+    pub fn edge_flows(&self) -> impl Iterator<Item = (WeakNode<T, E>, WeakNode<T, E>, &E::Amount)> {
+        self.flow.iter().map(|(&(start, end, _), flow)| {
+            (self.graph.handle(start), self.graph.handle(end), flow)
+        })
+    }
+}
@@ -0,0 +1,257 @@
+use std::ops::{Deref, Index, IndexMut};
+
+use crate::{
+    EdgeHandle, EdgeRef, Graph, GraphError, IterMut, Kind, Multi, Node, NodeMut, WeakNode,
+};
+
+/// A directed graph that keeps parallel edges between the same two nodes.
+///
+/// Every connection adds a new edge with its own `EdgeHandle`, rather than replacing the weight of an edge already
+/// there. Every read-only method of `Graph` is available through `Deref` to a `Graph` of kind `Multi`, and sees each
+/// parallel edge, so `dijkstras` takes the cheapest of them and `Path::len` adds up the ones a path takes. Methods
+/// that ignore direction pair each edge with one back, so parallel edges in the same direction count separately
+/// there. A node is listed once among its neighbors for every edge to it, and `edge_weight` gives the weight of the
+/// earliest edge between two nodes.
+///
+/// Cloning a graph gives the clone an identity of its own, as with `Graph`, and `translate_edge` finds the edge of
+/// the clone that an `EdgeHandle` of the original refers to.
+#[derive(Clone)]
+pub struct MultiGraph<T, E = ()> {
+    graph: Graph<T, E, Multi>,
+}
+
+impl<T, E> Default for MultiGraph<T, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, E> MultiGraph<T, E> {
+    /// Create a multigraph with no nodes
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            graph: Graph::empty(),
+        }
+    }
+
+    /// Add a node holding `value` to the graph
+    pub fn insert(&mut self, value: T) -> Node<'_, T, E, Multi> {
+        self.graph.insert(value)
+    }
+
+    /// Add an edge with a weight from `start` to `end`, alongside any edges already between them
+    ///
+    /// # Panics
+    ///
+    /// Panics if either the start or end node is not a live node of this graph.
+    pub fn connect_weighted(
+        &mut self,
+        start: WeakNode<T, E>,
+        end: WeakNode<T, E>,
+        weight: E,
+    ) -> EdgeHandle<T, E> {
+        self.try_connect_weighted(start, end, weight)
+            .unwrap_or_else(|err| {
+                panic!("Attempt to create connection with a node that is not part of this graph: {err}")
+            })
+    }
+
+    /// Add an edge with a weight from `start` to `end`, alongside any edges already between them
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either the start or end node is not a live node of this graph.
+    pub fn try_connect_weighted(
+        &mut self,
+        start: WeakNode<T, E>,
+        end: WeakNode<T, E>,
+        weight: E,
+    ) -> Result<EdgeHandle<T, E>, GraphError> {
+        let id = self
+            .graph
+            .add_edge(self.graph.resolve(start)?, self.graph.resolve(end)?, weight);
+        Ok(EdgeHandle { start, end, id })
+    }
+
+    /// Remove every edge from `start` to `end`, returning their weights in the order the edges were added.
+    pub fn disconnect(&mut self, start: WeakNode<T, E>, end: WeakNode<T, E>) -> Vec<E> {
+        let mut weights = Vec::new();
+        while let Some(weight) = self.graph.disconnect(start, end) {
+            weights.push(weight);
+        }
+        weights
+    }
+
+    /// Remove a single edge, returning its weight if it still exists.
+    pub fn remove_edge(&mut self, edge: EdgeHandle<T, E>) -> Option<E> {
+        let (start, end) = self.graph.resolve_edge(edge)?;
+        self.graph.remove_edge(start, end, edge.id)
+    }
+
+    /// Returns the edges from `start` to `end`, in the order they were added.
+    #[must_use]
+    pub fn edges_between(
+        &self,
+        start: WeakNode<T, E>,
+        end: WeakNode<T, E>,
+    ) -> Vec<EdgeRef<'_, T, E, Multi>> {
+        let Ok(start) = self.graph.try_weak_ref(start) else {
+            return Vec::new();
+        };
+        let Ok(end) = self.graph.resolve(end) else {
+            return Vec::new();
+        };
+        start
+            .edges()
+            .filter(|edge| edge.target.idx == end)
+            .collect()
+    }
+
+    /// Find the edge of this graph that an `EdgeHandle` of a copy of it refers to, as `translate` does for nodes,
+    /// if the edge exists in both.
+    #[must_use]
+    pub fn translate_edge(&self, edge: EdgeHandle<T, E>) -> Option<EdgeHandle<T, E>> {
+        let edge = EdgeHandle {
+            start: self.graph.try_translate(edge.start).ok()?,
+            end: self.graph.try_translate(edge.end).ok()?,
+            id: edge.id,
+        };
+        self.graph.resolve_edge(edge).map(|_| edge)
+    }
+
+    /// Returns the weight of a single edge, if it still exists.
+    #[must_use]
+    pub fn weight(&self, edge: EdgeHandle<T, E>) -> Option<&E> {
+        let (start, end) = self.graph.resolve_edge(edge)?;
+        self.graph.adjacency(start).edges.get(&(end, edge.id))
+    }
+
+    /// Returns the weight of a single edge mutably, if it still exists.
+    pub fn weight_mut(&mut self, edge: EdgeHandle<T, E>) -> Option<&mut E> {
+        let (start, end) = self.graph.resolve_edge(edge)?;
+        self.graph
+            .adjacency_mut(start)
+            .edges
+            .get_mut(&(end, edge.id))
+    }
+
+    /// Remove a node and every edge to or from it, returning its value.
+    ///
+    /// Weak references to other nodes remain valid, as with `Graph::remove_node`, while handles to the removed
+    /// edges no longer resolve.
+    pub fn remove_node(&mut self, node: WeakNode<T, E>) -> Option<T> {
+        self.graph.remove_node(node)
+    }
+
+    /// Convert a weak reference to a strong mutable reference. See `WeakNode` and `NodeMut` for more information.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `WeakNode` is from a different graph or refers to a node that has been removed.
+    #[must_use]
+    pub fn weak_mut(&mut self, node: WeakNode<T, E>) -> NodeMut<'_, T, E, Multi> {
+        self.graph.weak_mut(node)
+    }
+
+    /// Convert a weak reference to a strong mutable reference. See `WeakNode` and `NodeMut` for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `WeakNode` is from a different graph or refers to a node that has been removed.
+    pub fn try_weak_mut(
+        &mut self,
+        node: WeakNode<T, E>,
+    ) -> Result<NodeMut<'_, T, E, Multi>, GraphError> {
+        self.graph.try_weak_mut(node)
+    }
+
+    /// Returns an iterator over the value of every node of the graph mutably, along with its weak reference, in the
+    /// order of their slots.
+    pub fn iter_mut(&mut self) -> IterMut<'_, T, E> {
+        self.graph.iter_mut()
+    }
+
+    /// Find a minimum spanning forest with Kruskal's algorithm. See `Graph::minimum_spanning_tree`.
+    ///
+    /// Only the cheapest of a set of parallel edges can be part of the forest, and each tree edge is a pair of edges,
+    /// one in each direction.
+    #[must_use]
+    pub fn minimum_spanning_tree(&self) -> Self
+    where
+        T: Clone,
+        E: Clone + Ord,
+    {
+        Self {
+            graph: self.graph.minimum_spanning_tree(),
+        }
+    }
+
+    /// Find a minimum spanning forest with Prim's algorithm. See `Graph::minimum_spanning_tree_prim`.
+    #[must_use]
+    pub fn minimum_spanning_tree_prim(&self) -> Self
+    where
+        T: Clone,
+        E: Clone + Ord,
+    {
+        Self {
+            graph: self.graph.minimum_spanning_tree_prim(),
+        }
+    }
+}
+
+impl<T> MultiGraph<T> {
+    /// Add an edge from `start` to `end`, alongside any edges already between them
+    ///
+    /// # Panics
+    ///
+    /// Panics if either the start or end node is not a live node of this graph.
+    pub fn connect(&mut self, start: WeakNode<T>, end: WeakNode<T>) -> EdgeHandle<T> {
+        self.connect_weighted(start, end, ())
+    }
+}
+
+impl<T, E, K: Kind> Graph<T, E, K> {
+    /// Find the stored ends of the edge an `EdgeHandle` refers to, if both are live and the edge still exists
+    fn resolve_edge(&self, edge: EdgeHandle<T, E>) -> Option<(usize, usize)> {
+        let start = self.resolve(edge.start).ok()?;
+        let end = self.resolve(edge.end).ok()?;
+        if self.adjacency(start).edges.contains_key(&(end, edge.id)) {
+            Some((start, end))
+        } else if K::UNDIRECTED && self.adjacency(end).edges.contains_key(&(start, edge.id)) {
+            Some((end, start))
+        } else {
+            None
+        }
+    }
+}
+
+impl<T, E> Deref for MultiGraph<T, E> {
+    type Target = Graph<T, E, Multi>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.graph
+    }
+}
+
+impl<T, E> Index<WeakNode<T, E>> for MultiGraph<T, E> {
+    type Output = T;
+
+    fn index(&self, node: WeakNode<T, E>) -> &Self::Output {
+        &self.graph[node]
+    }
+}
+
+impl<T, E> IndexMut<WeakNode<T, E>> for MultiGraph<T, E> {
+    fn index_mut(&mut self, node: WeakNode<T, E>) -> &mut Self::Output {
+        &mut self.graph[node]
+    }
+}
+
+impl<'a, T, E> IntoIterator for &'a mut MultiGraph<T, E> {
+    type IntoIter = IterMut<'a, T, E>;
+    type Item = (WeakNode<T, E>, &'a mut T);
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
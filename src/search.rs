@@ -0,0 +1,212 @@
+use std::{cmp::Reverse, collections::BinaryHeap, ops::Add};
+
+use crate::{Graph, Path};
+
+/// The result of a best-first search over the node pool of a `Graph`
+pub struct Search<W> {
+    /// The best known distance from the start to each slot
+    pub distance: Vec<Option<W>>,
+    /// The node each slot was reached from on its best known path, along with the id of the edge that was taken
+    pub predecessor: Vec<Option<(usize, usize)>>,
+    /// Nodes in the order they were settled. Dijkstra's algorithm and Bellman-Ford list each reachable node once, in
+    /// nondecreasing distance, while A* lists every expansion.
+    pub settled: Vec<usize>,
+}
+
+impl<W> Search<W> {
+    /// Follow the predecessors back from `end` to build the path from `start`.
+    pub fn path<'a, T, E, K>(
+        &self,
+        graph: &'a Graph<T, E, K>,
+        start: usize,
+        end: usize,
+    ) -> Option<Path<'a, T, E, K>> {
+        self.distance[end].as_ref()?;
+        let mut prev = end;
+        let mut path = Vec::new();
+        let mut edges = Vec::new();
+        while prev != start {
+            path.push(prev);
+            let edge;
+            (prev, edge) = self.predecessor[prev]?;
+            edges.push(edge);
+        }
+        path.push(prev);
+        path.reverse();
+        edges.reverse();
+        Some(Path {
+            graph,
+            nodes: path,
+            edges,
+        })
+    }
+}
+
+/// Dijkstra's algorithm over `len` slots, using a binary heap with lazy deletion.
+///
+/// `edges` yields the neighbors of a node along with the id and weight of the edge to each. If `target` is given, the
+/// search stops as soon as it is settled.
+pub fn dijkstra<W, I>(
+    len: usize,
+    start: usize,
+    target: Option<usize>,
+    mut edges: impl FnMut(usize) -> I,
+) -> Search<W>
+where
+    W: Default + Clone + Ord + Add<W, Output = W>,
+    I: IntoIterator<Item = (usize, usize, W)>,
+{
+    let mut distance = vec![None; len];
+    let mut predecessor = vec![None; len];
+    let mut settled = Vec::new();
+    let mut done = vec![false; len];
+    let mut queue = BinaryHeap::new();
+    distance[start] = Some(W::default());
+    queue.push(Reverse((W::default(), start)));
+
+    while let Some(Reverse((dist, node))) = queue.pop() {
+        // entries are never removed from the heap, so skip the ones that have been superseded
+        if done[node] {
+            continue;
+        }
+        done[node] = true;
+        settled.push(node);
+        if target == Some(node) {
+            break;
+        }
+        for (next, edge, weight) in edges(node) {
+            if done[next] {
+                continue;
+            }
+            let new_dist = dist.clone() + weight;
+            if distance[next]
+                .as_ref()
+                .is_some_and(|old: &W| old <= &new_dist)
+            {
+                continue;
+            }
+            distance[next] = Some(new_dist.clone());
+            predecessor[next] = Some((node, edge));
+            queue.push(Reverse((new_dist, next)));
+        }
+    }
+    Search {
+        distance,
+        predecessor,
+        settled,
+    }
+}
+
+/// A* search over `len` slots towards `target`, guided by `heuristic`.
+///
+/// Nodes are reopened when a shorter path to them is found, so the result is optimal whenever the heuristic never
+/// overestimates the remaining distance, even if it is not consistent.
+pub fn astar<W, I>(
+    len: usize,
+    start: usize,
+    target: usize,
+    mut edges: impl FnMut(usize) -> I,
+    mut heuristic: impl FnMut(usize) -> W,
+) -> Search<W>
+where
+    W: Default + Clone + Ord + Add<W, Output = W>,
+    I: IntoIterator<Item = (usize, usize, W)>,
+{
+    let mut distance = vec![None; len];
+    let mut predecessor = vec![None; len];
+    let mut settled = Vec::new();
+    let mut queue = BinaryHeap::new();
+    distance[start] = Some(W::default());
+    queue.push(Reverse((heuristic(start), W::default(), start)));
+
+    while let Some(Reverse((_, dist, node))) = queue.pop() {
+        // skip entries for nodes that have since been reached by a shorter path
+        if distance[node].as_ref() != Some(&dist) {
+            continue;
+        }
+        settled.push(node);
+        if node == target {
+            break;
+        }
+        for (next, edge, weight) in edges(node) {
+            let new_dist = dist.clone() + weight;
+            if distance[next]
+                .as_ref()
+                .is_some_and(|old: &W| old <= &new_dist)
+            {
+                continue;
+            }
+            distance[next] = Some(new_dist.clone());
+            predecessor[next] = Some((node, edge));
+            queue.push(Reverse((
+                new_dist.clone() + heuristic(next),
+                new_dist,
+                next,
+            )));
+        }
+    }
+    Search {
+        distance,
+        predecessor,
+        settled,
+    }
+}
+
+/// The Bellman-Ford algorithm over the `nodes` given, starting from whatever distances are already known.
+///
+/// If a negative cycle is reachable, returns the nodes of one such cycle, with the first node repeated at the end,
+/// along with the ids of the edges between them.
+pub fn bellman_ford<W, I>(
+    nodes: &[usize],
+    distance: &mut [Option<W>],
+    predecessor: &mut [Option<(usize, usize)>],
+    mut edges: impl FnMut(usize) -> I,
+) -> Option<(Vec<usize>, Vec<usize>)>
+where
+    W: Clone + Ord + Add<W, Output = W>,
+    I: IntoIterator<Item = (usize, usize, W)>,
+{
+    let mut changed = None;
+    for _ in 0..nodes.len() {
+        changed = None;
+        for &node in nodes {
+            let Some(dist) = distance[node].clone() else {
+                continue;
+            };
+            for (next, edge, weight) in edges(node) {
+                let new_dist = dist.clone() + weight;
+                if distance[next]
+                    .as_ref()
+                    .is_some_and(|old: &W| old <= &new_dist)
+                {
+                    continue;
+                }
+                distance[next] = Some(new_dist);
+                predecessor[next] = Some((node, edge));
+                changed = Some(next);
+            }
+        }
+        changed?;
+    }
+    // distances were still improving after every simple path had been considered, so the predecessors of the
+    // last node to change lead into a negative cycle
+    let mut node = changed?;
+    for _ in 0..nodes.len() {
+        (node, _) = predecessor[node]?;
+    }
+    let mut cycle = vec![node];
+    let mut edges = Vec::new();
+    let mut prev = node;
+    loop {
+        let edge;
+        (prev, edge) = predecessor[prev]?;
+        cycle.push(prev);
+        edges.push(edge);
+        if prev == node {
+            break;
+        }
+    }
+    cycle.reverse();
+    edges.reverse();
+    Some((cycle, edges))
+}
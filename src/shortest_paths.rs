@@ -0,0 +1,219 @@
+use std::{
+    error::Error,
+    fmt::{Debug, Display},
+    ops::{Add, Deref},
+    slice,
+};
+
+use crate::{search, search::Search, Cycle, Directed, Graph, Kind, Node, Path, WeakNode};
+
+impl<T, E, K: Kind> Graph<T, E, K> {
+    /// Find the shortest paths from one node to every node reachable from it.
+    ///
+    /// This does the work of `dijkstras` once for all targets, without stopping early.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start node is not part of this graph.
+    #[must_use]
+    pub fn shortest_path_tree(&self, start: Node<'_, T, E, K>) -> ShortestPaths<'_, T, E, K>
+    where
+        E: Default + Clone + Ord + Add<E, Output = E>,
+    {
+        self.check(start).unwrap_or_else(|err| {
+            panic!("Attempt to generate paths for node outside of graph: {err}")
+        });
+        ShortestPaths {
+            graph: self,
+            start: start.idx,
+            search: search::dijkstra(self.nodes.len(), start.idx, None, |node| {
+                self.weighted_edges(node)
+            }),
+        }
+    }
+
+    /// Find the shortest paths from one node to every node reachable from it, allowing negative edge weights.
+    ///
+    /// This takes O(V * E) time, so prefer `shortest_path_tree` when no weight is negative.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `NegativeCycle` if a cycle whose total weight is negative can be reached from the start node, since
+    /// paths through it have no shortest length.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start node is not part of this graph.
+    pub fn bellman_ford(
+        &self,
+        start: Node<'_, T, E, K>,
+    ) -> Result<ShortestPaths<'_, T, E, K>, NegativeCycle<'_, T, E, K>>
+    where
+        E: Default + Clone + Ord + Add<E, Output = E>,
+    {
+        self.check(start).unwrap_or_else(|err| {
+            panic!("Attempt to generate paths for node outside of graph: {err}")
+        });
+        let nodes: Vec<usize> = self.indices().collect();
+        let mut distance = vec![None; self.nodes.len()];
+        let mut predecessor = vec![None; self.nodes.len()];
+        distance[start.idx] = Some(E::default());
+        if let Some((path, edges)) =
+            search::bellman_ford(&nodes, &mut distance, &mut predecessor, |node| {
+                self.weighted_edges(node)
+            })
+        {
+            return Err(NegativeCycle::new(Path {
+                graph: self,
+                nodes: path,
+                edges,
+            }));
+        }
+        let mut settled: Vec<usize> = nodes
+            .into_iter()
+            .filter(|&idx| distance[idx].is_some())
+            .collect();
+        settled.sort_by(|&a, &b| distance[a].cmp(&distance[b]));
+        Ok(ShortestPaths {
+            graph: self,
+            start: start.idx,
+            search: Search {
+                distance,
+                predecessor,
+                settled,
+            },
+        })
+    }
+}
+
+/// A cycle whose total weight is negative, which prevents shortest paths from being defined.
+///
+/// The path around the cycle is reached through `Deref` to `Cycle`.
+pub struct NegativeCycle<'a, T, E, K = Directed> {
+    cycle: Cycle<'a, T, E, K>,
+}
+
+impl<'a, T, E, K: Kind> NegativeCycle<'a, T, E, K> {
+    pub(crate) const fn new(cycle: Path<'a, T, E, K>) -> Self {
+        Self {
+            cycle: Cycle::new(cycle),
+        }
+    }
+
+    /// Returns the underlying `Cycle`
+    #[must_use]
+    pub fn into_cycle(self) -> Cycle<'a, T, E, K> {
+        self.cycle
+    }
+}
+
+impl<'a, T, E, K: Kind> Deref for NegativeCycle<'a, T, E, K> {
+    type Target = Cycle<'a, T, E, K>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.cycle
+    }
+}
+
+impl<T: Debug, E, K: Kind> Debug for NegativeCycle<'_, T, E, K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("NegativeCycle")
+            .field(self.cycle.cycle())
+            .finish()
+    }
+}
+
+impl<T: Debug, E, K: Kind> Display for NegativeCycle<'_, T, E, K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "graph contains a negative cycle: {:?}",
+            self.cycle.cycle()
+        )
+    }
+}
+
+impl<T: Debug, E, K: Kind> Error for NegativeCycle<'_, T, E, K> {}
+
+/// The shortest paths from a single node to every node reachable from it
+pub struct ShortestPaths<'a, T, E, K = Directed> {
+    graph: &'a Graph<T, E, K>,
+    start: usize,
+    search: Search<E>,
+}
+
+impl<'a, T, E, K: Kind> ShortestPaths<'a, T, E, K> {
+    /// Returns the node these paths start from
+    #[must_use]
+    pub const fn start(&self) -> Node<'a, T, E, K> {
+        Node {
+            graph: self.graph,
+            idx: self.start,
+        }
+    }
+
+    /// Returns the length of the shortest path to `node`, or `None` if it is unreachable or not part of the graph.
+    #[must_use]
+    pub fn distance_to(&self, node: WeakNode<T, E>) -> Option<&E> {
+        let idx = self.graph.resolve(node).ok()?;
+        self.search.distance[idx].as_ref()
+    }
+
+    /// Returns the node `node` is reached from on its shortest path, or `None` if it is the start, unreachable or
+    /// not part of the graph.
+    #[must_use]
+    pub fn predecessor(&self, node: WeakNode<T, E>) -> Option<Node<'a, T, E, K>> {
+        let idx = self.graph.resolve(node).ok()?;
+        Some(Node {
+            graph: self.graph,
+            idx: self.search.predecessor[idx]?.0,
+        })
+    }
+
+    /// Returns the shortest path to `node`, or `None` if it is unreachable or not part of the graph.
+    #[must_use]
+    pub fn path_to(&self, node: WeakNode<T, E>) -> Option<Path<'a, T, E, K>> {
+        let idx = self.graph.resolve(node).ok()?;
+        self.search.path(self.graph, self.start, idx)
+    }
+
+    /// Returns an iterator over the reachable nodes and their distances, nearest first.
+    #[must_use]
+    pub fn iter(&self) -> Settled<'_, 'a, T, E, K> {
+        Settled {
+            graph: self.graph,
+            distance: &self.search.distance,
+            iter: self.search.settled.iter(),
+        }
+    }
+}
+
+impl<'s, 'a, T, E, K: Kind> IntoIterator for &'s ShortestPaths<'a, T, E, K> {
+    type IntoIter = Settled<'s, 'a, T, E, K>;
+    type Item = (Node<'a, T, E, K>, &'s E);
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the nodes of a `ShortestPaths` in order of distance
+pub struct Settled<'s, 'a, T, E, K = Directed> {
+    graph: &'a Graph<T, E, K>,
+    distance: &'s [Option<E>],
+    iter: slice::Iter<'s, usize>,
+}
+
+impl<'s, 'a, T, E, K: Kind> Iterator for Settled<'s, 'a, T, E, K> {
+    type Item = (Node<'a, T, E, K>, &'s E);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let idx = *self.iter.next()?;
+        Some((
+            Node {
+                graph: self.graph,
+                idx,
+            },
+            self.distance[idx].as_ref()?,
+        ))
+    }
+}
@@ -0,0 +1,94 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use crate::{union_find::UnionFind, Graph, Kind};
+
+impl<T, E, K: Kind> Graph<T, E, K> {
+    /// Find a minimum spanning forest with Kruskal's algorithm, treating every edge as undirected.
+    ///
+    /// The result has the same nodes as this graph but an identity of its own, so `WeakNode`s of this graph need to
+    /// go through `translate` to be used with it. Each tree edge is an undirected connection. Kruskal's algorithm
+    /// sorts every edge up front, taking O(E log E) time, which suits sparse graphs; see `minimum_spanning_tree_prim`
+    /// for dense ones.
+    #[must_use]
+    pub fn minimum_spanning_tree(&self) -> Self
+    where
+        T: Clone,
+        E: Clone + Ord,
+    {
+        let mut edges: Vec<(&E, usize, usize)> = self
+            .indices()
+            .flat_map(|idx| {
+                self.edges_from(idx)
+                    .filter(move |&(next, _, _)| next != idx)
+                    .map(move |(next, _, weight)| (weight, idx, next))
+            })
+            .collect();
+        edges.sort();
+
+        let mut sets = UnionFind::new(self.nodes.len());
+        let mut tree = self.without_edges();
+        for (weight, start, end) in edges {
+            if sets.union(start, end) {
+                tree.connect_undirected_weighted(
+                    tree.handle(start),
+                    tree.handle(end),
+                    weight.clone(),
+                );
+            }
+        }
+        tree
+    }
+
+    /// Find a minimum spanning forest with Prim's algorithm, treating every edge as undirected.
+    ///
+    /// This gives a forest of the same total weight as `minimum_spanning_tree`. Prim's algorithm grows one tree at a
+    /// time from a binary heap of the edges leaving it, so it only keeps the frontier in memory and tends to be
+    /// faster on dense graphs.
+    #[must_use]
+    pub fn minimum_spanning_tree_prim(&self) -> Self
+    where
+        T: Clone,
+        E: Clone + Ord,
+    {
+        let mut neighbors: Vec<Vec<(usize, &E)>> = vec![Vec::new(); self.nodes.len()];
+        for idx in self.indices() {
+            for (next, _, weight) in self.edges_from(idx) {
+                if next != idx {
+                    neighbors[idx].push((next, weight));
+                    neighbors[next].push((idx, weight));
+                }
+            }
+        }
+
+        let mut in_tree = vec![false; self.nodes.len()];
+        let mut tree = self.without_edges();
+        for root in self.indices() {
+            if in_tree[root] {
+                continue;
+            }
+            in_tree[root] = true;
+            let mut frontier: BinaryHeap<_> = neighbors[root]
+                .iter()
+                .map(|&(next, weight)| Reverse((weight, root, next)))
+                .collect();
+            while let Some(Reverse((weight, start, end))) = frontier.pop() {
+                if in_tree[end] {
+                    continue;
+                }
+                in_tree[end] = true;
+                tree.connect_undirected_weighted(
+                    tree.handle(start),
+                    tree.handle(end),
+                    weight.clone(),
+                );
+                frontier.extend(
+                    neighbors[end]
+                        .iter()
+                        .filter(|&&(next, _)| !in_tree[next])
+                        .map(|&(next, weight)| Reverse((weight, end, next))),
+                );
+            }
+        }
+        tree
+    }
+}
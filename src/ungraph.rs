@@ -0,0 +1,200 @@
+use std::ops::{Deref, Index, IndexMut};
+
+use crate::{EulerError, Graph, GraphError, IterMut, Node, NodeMut, Path, Undirected, WeakNode};
+
+/// An undirected graph whose nodes hold a `T` and whose edges hold an `E`.
+///
+/// Each edge and its weight is stored once, and can be followed, looked up or removed from either end. Every
+/// read-only method of `Graph` is available through `Deref` to a `Graph` of kind `Undirected`, which treats an edge
+/// as leading both ways, so searches like `dijkstras`, `breadth_first` and `depth_first` work unchanged. A node's
+/// neighbors and predecessors are the same, and its in and out degree both count a loop once.
+///
+/// Cloning a graph gives the clone an identity of its own, as with `Graph`.
+#[derive(Clone)]
+pub struct UnGraph<T, E = ()> {
+    graph: Graph<T, E, Undirected>,
+}
+
+impl<T, E> Default for UnGraph<T, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, E> UnGraph<T, E> {
+    /// Create an undirected graph with no nodes
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            graph: Graph::empty(),
+        }
+    }
+
+    /// Add a node holding `value` to the graph
+    pub fn insert(&mut self, value: T) -> Node<'_, T, E, Undirected> {
+        self.graph.insert(value)
+    }
+
+    /// Connect two nodes with a weight, replacing the weight of any edge already between them
+    ///
+    /// # Panics
+    ///
+    /// Panics if either node is not a live node of this graph.
+    pub fn connect_weighted(&mut self, a: WeakNode<T, E>, b: WeakNode<T, E>, weight: E) {
+        self.graph.connect_weighted(a, b, weight);
+    }
+
+    /// Connect two nodes with a weight, replacing the weight of any edge already between them
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either node is not a live node of this graph.
+    pub fn try_connect_weighted(
+        &mut self,
+        a: WeakNode<T, E>,
+        b: WeakNode<T, E>,
+        weight: E,
+    ) -> Result<(), GraphError> {
+        self.graph.try_connect_weighted(a, b, weight)
+    }
+
+    /// Remove the edge between `a` and `b`, whichever end it was connected from, returning its weight if it
+    /// existed.
+    pub fn disconnect(&mut self, a: WeakNode<T, E>, b: WeakNode<T, E>) -> Option<E> {
+        self.graph.disconnect(a, b)
+    }
+
+    /// Returns the weight of the edge between `a` and `b` mutably, if both are live nodes of this graph and the
+    /// edge exists.
+    pub fn edge_weight_mut(&mut self, a: WeakNode<T, E>, b: WeakNode<T, E>) -> Option<&mut E> {
+        self.graph.edge_weight_mut(a, b)
+    }
+
+    /// Remove a node and every edge to it, returning its value.
+    ///
+    /// Weak references to other nodes remain valid, as with `Graph::remove_node`.
+    pub fn remove_node(&mut self, node: WeakNode<T, E>) -> Option<T> {
+        self.graph.remove_node(node)
+    }
+
+    /// Convert a weak reference to a strong mutable reference. See `WeakNode` and `NodeMut` for more information.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `WeakNode` is from a different graph or refers to a node that has been removed.
+    #[must_use]
+    pub fn weak_mut(&mut self, node: WeakNode<T, E>) -> NodeMut<'_, T, E, Undirected> {
+        self.graph.weak_mut(node)
+    }
+
+    /// Convert a weak reference to a strong mutable reference. See `WeakNode` and `NodeMut` for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `WeakNode` is from a different graph or refers to a node that has been removed.
+    pub fn try_weak_mut(
+        &mut self,
+        node: WeakNode<T, E>,
+    ) -> Result<NodeMut<'_, T, E, Undirected>, GraphError> {
+        self.graph.try_weak_mut(node)
+    }
+
+    /// Returns an iterator over the value of every node of the graph mutably, along with its weak reference, in the
+    /// order of their slots.
+    pub fn iter_mut(&mut self) -> IterMut<'_, T, E> {
+        self.graph.iter_mut()
+    }
+
+    /// Find a circuit from `start` back to itself that follows every edge exactly once, using Hierholzer's
+    /// algorithm in O(V + E) time. A loop counts twice towards the degree of its node.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start node is not part of this graph.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a node has an odd degree, or if some edge can't be reached from `start`.
+    pub fn eulerian_circuit(
+        &self,
+        start: Node<'_, T, E, Undirected>,
+    ) -> Result<Path<'_, T, E, Undirected>, EulerError<T, E>> {
+        self.graph.undirected_eulerian_circuit(start)
+    }
+
+    /// Find a walk that follows every edge exactly once, using Hierholzer's algorithm in O(V + E) time.
+    ///
+    /// The walk is a circuit if possible, and otherwise runs between the two nodes of odd degree.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if more than two nodes have an odd degree, or if the edges are not all connected.
+    pub fn eulerian_path(&self) -> Result<Path<'_, T, E, Undirected>, EulerError<T, E>> {
+        self.graph.undirected_eulerian_path()
+    }
+
+    /// Find a minimum spanning forest with Kruskal's algorithm. See `Graph::minimum_spanning_tree`.
+    #[must_use]
+    pub fn minimum_spanning_tree(&self) -> Self
+    where
+        T: Clone,
+        E: Clone + Ord,
+    {
+        Self {
+            graph: self.graph.minimum_spanning_tree(),
+        }
+    }
+
+    /// Find a minimum spanning forest with Prim's algorithm. See `Graph::minimum_spanning_tree_prim`.
+    #[must_use]
+    pub fn minimum_spanning_tree_prim(&self) -> Self
+    where
+        T: Clone,
+        E: Clone + Ord,
+    {
+        Self {
+            graph: self.graph.minimum_spanning_tree_prim(),
+        }
+    }
+}
+
+impl<T> UnGraph<T> {
+    /// Create a connection between the two input vertices
+    ///
+    /// # Panics
+    ///
+    /// Panics if either node is not a live node of this graph.
+    pub fn connect(&mut self, a: WeakNode<T>, b: WeakNode<T>) {
+        self.connect_weighted(a, b, ());
+    }
+}
+
+impl<T, E> Deref for UnGraph<T, E> {
+    type Target = Graph<T, E, Undirected>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.graph
+    }
+}
+
+impl<T, E> Index<WeakNode<T, E>> for UnGraph<T, E> {
+    type Output = T;
+
+    fn index(&self, node: WeakNode<T, E>) -> &Self::Output {
+        &self.graph[node]
+    }
+}
+
+impl<T, E> IndexMut<WeakNode<T, E>> for UnGraph<T, E> {
+    fn index_mut(&mut self, node: WeakNode<T, E>) -> &mut Self::Output {
+        &mut self.graph[node]
+    }
+}
+
+impl<'a, T, E> IntoIterator for &'a mut UnGraph<T, E> {
+    type IntoIter = IterMut<'a, T, E>;
+    type Item = (WeakNode<T, E>, &'a mut T);
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
@@ -0,0 +1,58 @@
+/// A disjoint-set forest over slot indices, with union by size and path halving
+#[derive(Clone, Debug)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+}
+
+impl UnionFind {
+    pub fn new(len: usize) -> Self {
+        Self {
+            parent: (0..len).collect(),
+            size: vec![1; len],
+        }
+    }
+
+    pub const fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    /// Make room for the slots up to `len`, each in a set of its own
+    pub fn grow(&mut self, len: usize) {
+        while self.parent.len() < len {
+            self.parent.push(self.parent.len());
+            self.size.push(1);
+        }
+    }
+
+    /// Find the representative of the set containing `idx` without modifying the forest
+    pub fn root(&self, mut idx: usize) -> usize {
+        while self.parent[idx] != idx {
+            idx = self.parent[idx];
+        }
+        idx
+    }
+
+    /// Find the representative of the set containing `idx`, shortening the path to it on the way
+    pub fn find(&mut self, mut idx: usize) -> usize {
+        while self.parent[idx] != idx {
+            self.parent[idx] = self.parent[self.parent[idx]];
+            idx = self.parent[idx];
+        }
+        idx
+    }
+
+    /// Merge the sets containing `a` and `b`, returning whether they were separate
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        if self.size[a] < self.size[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        true
+    }
+}
@@ -0,0 +1,636 @@
+use std::ops::{Add, Div, Neg, Sub};
+
+/// Label of a vertex or top level blossom that is not in the alternating forest
+const FREE: u8 = 0;
+/// Label of a vertex or top level blossom at an even distance from a free vertex
+const OUTER: u8 = 1;
+/// Label of a vertex or top level blossom at an odd distance from a free vertex
+const INNER: u8 = 2;
+/// Marks outer blossoms already passed while looking for a new blossom's base
+const SCANNED: u8 = 4;
+
+/// Find a perfect matching of minimum total weight among `len` vertices, with Edmonds' weighted blossom algorithm
+/// in O(V³) time.
+///
+/// Returns the vertex matched to each vertex, or `None` if the edges don't allow every vertex to be matched. The
+/// dual variables can go below zero, so `W` has to be a signed type, as the `Neg` bound ensures.
+pub fn minimum_weight_perfect_matching<W>(
+    len: usize,
+    edges: Vec<(usize, usize, W)>,
+) -> Option<Vec<usize>>
+where
+    W: Default
+        + Clone
+        + Ord
+        + Add<W, Output = W>
+        + Sub<W, Output = W>
+        + Div<W, Output = W>
+        + Neg<Output = W>
+        + From<u8>,
+{
+    let heaviest = edges.iter().map(|(_, _, weight)| weight).max().cloned();
+    let Some(heaviest) = heaviest else {
+        return (len == 0).then(Vec::new);
+    };
+    // a matching of maximum weight among those of maximum size has minimum weight once the weights are flipped
+    let edges = edges
+        .into_iter()
+        .map(|(a, b, weight)| (a, b, heaviest.clone() - weight))
+        .collect();
+    let mut matching = Matching::new(len, edges);
+    matching.solve();
+    matching
+        .mate
+        .iter()
+        .map(|&end| end.map(|end| matching.endpoint[end]))
+        .collect()
+}
+
+/// The state of the weighted blossom algorithm, after the implementation by Joris van Rantwijk, which keeps the
+/// maximum weight matching among those of maximum size.
+///
+/// Edge `k` has the endpoints `2k` and `2k + 1`, and matchings and labels refer to the endpoint at the far end of
+/// an edge. Slots below `len` are vertices, while those above are blossoms.
+struct Matching<W> {
+    len: usize,
+    edges: Vec<(usize, usize, W)>,
+    /// The vertex at each endpoint
+    endpoint: Vec<usize>,
+    /// The far endpoints of the edges at each vertex
+    neighbor_ends: Vec<Vec<usize>>,
+    mate: Vec<Option<usize>>,
+    label: Vec<u8>,
+    /// The endpoint through which each labelled vertex or top level blossom was reached
+    label_end: Vec<Option<usize>>,
+    /// The top level blossom containing each vertex
+    in_blossom: Vec<usize>,
+    blossom_parent: Vec<Option<usize>>,
+    /// The sub-blossoms of each blossom in order around its cycle, starting with the one holding its base
+    blossom_children: Vec<Vec<usize>>,
+    blossom_base: Vec<Option<usize>>,
+    /// The endpoints joining each sub-blossom to the next, pointing away from the earlier one
+    blossom_ends: Vec<Vec<usize>>,
+    /// The least slack edge from each free vertex or outer top level blossom to a different outer blossom
+    best_edge: Vec<Option<usize>>,
+    /// The least slack edges from each outer top level blossom to each other outer blossom
+    blossom_best_edges: Vec<Option<Vec<usize>>>,
+    unused_blossoms: Vec<usize>,
+    /// Twice the dual variable of each vertex, or the dual variable of each blossom
+    dual: Vec<W>,
+    /// Whether each edge has zero slack, and may be used to grow the forest
+    allowed: Vec<bool>,
+    /// Outer vertices whose edges have not been scanned yet
+    queue: Vec<usize>,
+}
+
+impl<W> Matching<W>
+where
+    W: Default
+        + Clone
+        + Ord
+        + Add<W, Output = W>
+        + Sub<W, Output = W>
+        + Div<W, Output = W>
+        + From<u8>,
+{
+    fn new(len: usize, edges: Vec<(usize, usize, W)>) -> Self {
+        let mut neighbor_ends = vec![Vec::new(); len];
+        let mut endpoint = Vec::with_capacity(2 * edges.len());
+        for (k, (a, b, _)) in edges.iter().enumerate() {
+            endpoint.push(*a);
+            endpoint.push(*b);
+            neighbor_ends[*a].push(2 * k + 1);
+            neighbor_ends[*b].push(2 * k);
+        }
+        let heaviest = edges
+            .iter()
+            .map(|(_, _, weight)| weight.clone())
+            .max()
+            .unwrap_or_default();
+        let mut dual = vec![heaviest; len];
+        dual.resize(2 * len, W::default());
+        Self {
+            len,
+            allowed: vec![false; edges.len()],
+            edges,
+            endpoint,
+            neighbor_ends,
+            mate: vec![None; len],
+            label: vec![FREE; 2 * len],
+            label_end: vec![None; 2 * len],
+            in_blossom: (0..len).collect(),
+            blossom_parent: vec![None; 2 * len],
+            blossom_children: vec![Vec::new(); 2 * len],
+            blossom_base: (0..len).map(Some).chain((0..len).map(|_| None)).collect(),
+            blossom_ends: vec![Vec::new(); 2 * len],
+            best_edge: vec![None; 2 * len],
+            blossom_best_edges: vec![None; 2 * len],
+            unused_blossoms: (len..2 * len).collect(),
+            dual,
+            queue: Vec::new(),
+        }
+    }
+
+    /// Run one stage per augmentation until no augmenting path is left
+    fn solve(&mut self) {
+        for _ in 0..self.len {
+            self.label.fill(FREE);
+            self.best_edge.fill(None);
+            for best in &mut self.blossom_best_edges[self.len..] {
+                *best = None;
+            }
+            self.allowed.fill(false);
+            self.queue.clear();
+            for v in 0..self.len {
+                if self.mate[v].is_none() && self.label[self.in_blossom[v]] == FREE {
+                    self.assign_label(v, OUTER, None);
+                }
+            }
+
+            let augmented = loop {
+                if self.scan() {
+                    break true;
+                }
+                if !self.adjust_duals() {
+                    break false;
+                }
+            };
+            if !augmented {
+                break;
+            }
+
+            // blossoms whose dual variable has dropped to zero are no longer needed
+            for b in self.len..2 * self.len {
+                if self.blossom_parent[b].is_none()
+                    && self.blossom_base[b].is_some()
+                    && self.label[b] == OUTER
+                    && self.dual[b] == W::default()
+                {
+                    self.expand_blossom(b, true);
+                }
+            }
+        }
+    }
+
+    /// Twice the slack of edge `k`, which must join two different top level blossoms
+    fn slack(&self, k: usize) -> W {
+        let (a, b, weight) = &self.edges[k];
+        self.dual[*a].clone() + self.dual[*b].clone() - weight.clone() - weight.clone()
+    }
+
+    /// The vertices inside the blossom or vertex `b`
+    fn leaves(&self, b: usize) -> Vec<usize> {
+        let mut leaves = Vec::new();
+        let mut stack = vec![b];
+        while let Some(b) = stack.pop() {
+            if b < self.len {
+                leaves.push(b);
+            } else {
+                stack.extend(self.blossom_children[b].iter().rev());
+            }
+        }
+        leaves
+    }
+
+    /// Label the top level blossom containing `w`, reached through endpoint `end`, and the mate of an inner one
+    fn assign_label(&mut self, mut w: usize, mut label: u8, mut end: Option<usize>) {
+        loop {
+            let b = self.in_blossom[w];
+            self.label[w] = label;
+            self.label[b] = label;
+            self.label_end[w] = end;
+            self.label_end[b] = end;
+            self.best_edge[w] = None;
+            self.best_edge[b] = None;
+            if label == OUTER {
+                let leaves = self.leaves(b);
+                self.queue.extend(leaves);
+                return;
+            }
+            let Some(mate) = self.blossom_base[b].and_then(|base| self.mate[base]) else {
+                return;
+            };
+            w = self.endpoint[mate];
+            label = OUTER;
+            end = Some(mate ^ 1);
+        }
+    }
+
+    /// Scan the queued outer vertices for edges that grow the forest, close a blossom or augment the matching.
+    ///
+    /// Returns whether the matching was augmented.
+    fn scan(&mut self) -> bool {
+        while let Some(v) = self.queue.pop() {
+            for i in 0..self.neighbor_ends[v].len() {
+                let p = self.neighbor_ends[v][i];
+                let k = p / 2;
+                let w = self.endpoint[p];
+                if self.in_blossom[v] == self.in_blossom[w] {
+                    continue;
+                }
+                let mut slack = None;
+                if !self.allowed[k] {
+                    let k_slack = self.slack(k);
+                    self.allowed[k] = k_slack <= W::default();
+                    slack = Some(k_slack);
+                }
+                let w_label = self.label[self.in_blossom[w]];
+                if self.allowed[k] {
+                    if w_label == FREE {
+                        self.assign_label(w, INNER, Some(p ^ 1));
+                    } else if w_label == OUTER {
+                        if let Some(base) = self.scan_blossom(v, w) {
+                            self.add_blossom(base, k);
+                        } else {
+                            self.augment_matching(k);
+                            return true;
+                        }
+                    } else if self.label[w] == FREE {
+                        // w is inside an inner blossom and was not reached yet
+                        self.label[w] = INNER;
+                        self.label_end[w] = Some(p ^ 1);
+                    }
+                } else if w_label == OUTER {
+                    let b = self.in_blossom[v];
+                    self.improve_best_edge(b, k, slack);
+                } else if self.label[w] == FREE {
+                    self.improve_best_edge(w, k, slack);
+                }
+            }
+        }
+        false
+    }
+
+    fn improve_best_edge(&mut self, b: usize, k: usize, slack: Option<W>) {
+        let slack = slack.unwrap_or_else(|| self.slack(k));
+        if self.best_edge[b].is_none_or(|best| slack < self.slack(best)) {
+            self.best_edge[b] = Some(k);
+        }
+    }
+
+    /// Trace back from the outer vertices `v` and `w` towards their roots, returning the base of the new blossom if
+    /// the paths meet, or `None` if they reach different roots and form an augmenting path
+    fn scan_blossom(&mut self, v: usize, w: usize) -> Option<usize> {
+        let mut path = Vec::new();
+        let mut base = None;
+        let (mut v, mut w) = (Some(v), Some(w));
+        while let Some(current) = v {
+            let b = self.in_blossom[current];
+            if self.label[b] & SCANNED != 0 {
+                base = self.blossom_base[b];
+                break;
+            }
+            path.push(b);
+            self.label[b] = OUTER | SCANNED;
+            // step over the inner blossom above to the next outer one
+            v = self.label_end[b].and_then(|end| {
+                let inner = self.in_blossom[self.endpoint[end]];
+                self.label_end[inner].map(|end| self.endpoint[end])
+            });
+            if w.is_some() {
+                std::mem::swap(&mut v, &mut w);
+            }
+        }
+        for b in path {
+            self.label[b] = OUTER;
+        }
+        base
+    }
+
+    /// Form a new blossom with the given base, closed by edge `k` between two outer blossoms
+    fn add_blossom(&mut self, base: usize, k: usize) {
+        let (v, w, _) = self.edges[k];
+        let bb = self.in_blossom[base];
+        let mut bv = self.in_blossom[v];
+        let mut bw = self.in_blossom[w];
+        let Some(b) = self.unused_blossoms.pop() else {
+            return;
+        };
+        self.blossom_base[b] = Some(base);
+        self.blossom_parent[b] = None;
+        self.blossom_parent[bb] = Some(b);
+
+        let mut children = Vec::new();
+        let mut ends = Vec::new();
+        while bv != bb {
+            self.blossom_parent[bv] = Some(b);
+            children.push(bv);
+            let Some(end) = self.label_end[bv] else {
+                break;
+            };
+            ends.push(end);
+            bv = self.in_blossom[self.endpoint[end]];
+        }
+        children.push(bb);
+        children.reverse();
+        ends.reverse();
+        ends.push(2 * k);
+        while bw != bb {
+            self.blossom_parent[bw] = Some(b);
+            children.push(bw);
+            let Some(end) = self.label_end[bw] else {
+                break;
+            };
+            ends.push(end ^ 1);
+            bw = self.in_blossom[self.endpoint[end]];
+        }
+        self.blossom_children[b] = children;
+        self.blossom_ends[b] = ends;
+
+        self.label[b] = OUTER;
+        self.label_end[b] = self.label_end[bb];
+        self.dual[b] = W::default();
+        for v in self.leaves(b) {
+            // inner vertices become outer inside the blossom, so their edges need scanning
+            if self.label[self.in_blossom[v]] == INNER {
+                self.queue.push(v);
+            }
+            self.in_blossom[v] = b;
+        }
+        self.merge_best_edges(b);
+    }
+
+    /// Combine the least slack edges of the sub-blossoms of the new blossom `b`
+    fn merge_best_edges(&mut self, b: usize) {
+        let mut best_to: Vec<Option<usize>> = vec![None; 2 * self.len];
+        for i in 0..self.blossom_children[b].len() {
+            let child = self.blossom_children[b][i];
+            let candidates: Vec<usize> =
+                self.blossom_best_edges[child].take().unwrap_or_else(|| {
+                    self.leaves(child)
+                        .into_iter()
+                        .flat_map(|v| self.neighbor_ends[v].iter().map(|&p| p / 2))
+                        .collect()
+                });
+            for k in candidates {
+                let (i, j, _) = self.edges[k];
+                let j = if self.in_blossom[j] == b { i } else { j };
+                let bj = self.in_blossom[j];
+                if bj != b
+                    && self.label[bj] == OUTER
+                    && best_to[bj].is_none_or(|best| self.slack(k) < self.slack(best))
+                {
+                    best_to[bj] = Some(k);
+                }
+            }
+            self.best_edge[child] = None;
+        }
+        let best: Vec<usize> = best_to.into_iter().flatten().collect();
+        self.best_edge[b] = best
+            .iter()
+            .copied()
+            .min_by(|&a, &c| self.slack(a).cmp(&self.slack(c)));
+        self.blossom_best_edges[b] = Some(best);
+    }
+
+    /// Find the position of `child` in the cycle of `b`, and the direction to walk from it towards the base along
+    /// an even number of edges. The second value is one when walking backwards, where the endpoints are flipped.
+    fn walk_to_base(&self, b: usize, child: usize) -> (isize, isize, usize) {
+        let children = &self.blossom_children[b];
+        let position = children
+            .iter()
+            .position(|&c| c == child)
+            .unwrap_or_default();
+        let j = isize::try_from(position).unwrap_or_default();
+        if position % 2 == 1 {
+            (
+                j - isize::try_from(children.len()).unwrap_or_default(),
+                1,
+                0,
+            )
+        } else {
+            (j, -1, 1)
+        }
+    }
+
+    /// Wrap a possibly negative position around the cycle of `b`
+    fn around(&self, b: usize, j: isize) -> usize {
+        let len = isize::try_from(self.blossom_children[b].len()).unwrap_or(1);
+        usize::try_from(j.rem_euclid(len)).unwrap_or_default()
+    }
+
+    /// The endpoint joining position `j` of the cycle of `b` to its neighbor in the direction of the walk, before
+    /// flipping it to point along the walk
+    fn end_before(&self, b: usize, j: isize, flip: usize) -> usize {
+        let j = if flip == 1 { j - 1 } else { j };
+        self.blossom_ends[b][self.around(b, j)]
+    }
+
+    /// Undo the top level blossom `b`, relabelling its sub-blossoms if it was inner in the middle of a stage
+    fn expand_blossom(&mut self, b: usize, end_stage: bool) {
+        for i in 0..self.blossom_children[b].len() {
+            let s = self.blossom_children[b][i];
+            self.blossom_parent[s] = None;
+            if s < self.len {
+                self.in_blossom[s] = s;
+            } else if end_stage && self.dual[s] == W::default() {
+                self.expand_blossom(s, end_stage);
+            } else {
+                for v in self.leaves(s) {
+                    self.in_blossom[v] = s;
+                }
+            }
+        }
+        if !end_stage && self.label[b] == INNER {
+            if let Some(end) = self.label_end[b] {
+                self.relabel_expanded(b, end);
+            }
+        }
+        self.label[b] = FREE;
+        self.label_end[b] = None;
+        self.blossom_children[b].clear();
+        self.blossom_ends[b].clear();
+        self.blossom_base[b] = None;
+        self.blossom_best_edges[b] = None;
+        self.best_edge[b] = None;
+        self.unused_blossoms.push(b);
+    }
+
+    /// Label the sub-blossoms of the expanded inner blossom `b` along the even path from where the forest entered
+    /// it through `end` to its base, which keeps the alternating tree intact
+    fn relabel_expanded(&mut self, b: usize, end: usize) {
+        let entry = self.in_blossom[self.endpoint[end ^ 1]];
+        let (mut j, step, flip) = self.walk_to_base(b, entry);
+        let mut p = end;
+        while j != 0 {
+            self.label[self.endpoint[p ^ 1]] = FREE;
+            let q = self.end_before(b, j, flip);
+            self.label[self.endpoint[q ^ flip ^ 1]] = FREE;
+            self.assign_label(self.endpoint[p ^ 1], INNER, Some(p));
+            self.allowed[q / 2] = true;
+            j += step;
+            p = self.end_before(b, j, flip) ^ flip;
+            self.allowed[p / 2] = true;
+            j += step;
+        }
+        let bv = self.blossom_children[b][self.around(b, j)];
+        let v = self.endpoint[p ^ 1];
+        self.label[v] = INNER;
+        self.label[bv] = INNER;
+        self.label_end[v] = Some(p);
+        self.label_end[bv] = Some(p);
+        self.best_edge[bv] = None;
+        j += step;
+
+        // the sub-blossoms on the odd path are free again, unless one of their vertices was reached from outside
+        while self.blossom_children[b][self.around(b, j)] != entry {
+            let bv = self.blossom_children[b][self.around(b, j)];
+            j += step;
+            if self.label[bv] == OUTER {
+                continue;
+            }
+            if let Some(v) = self.leaves(bv).into_iter().find(|&v| self.label[v] != FREE) {
+                self.label[v] = FREE;
+                if let Some(mate) = self.blossom_base[bv].and_then(|base| self.mate[base]) {
+                    self.label[self.endpoint[mate]] = FREE;
+                }
+                self.assign_label(v, INNER, self.label_end[v]);
+            }
+        }
+    }
+
+    /// Swap matched and unmatched edges inside blossom `b` so that vertex `v` becomes its base
+    fn augment_blossom(&mut self, blossom: usize, vertex: usize) {
+        let mut child = vertex;
+        while let Some(parent) = self.blossom_parent[child].filter(|&parent| parent != blossom) {
+            child = parent;
+        }
+        if child >= self.len {
+            self.augment_blossom(child, vertex);
+        }
+        let (start, step, flip) = self.walk_to_base(blossom, child);
+        let mut j = start;
+        while j != 0 {
+            j += step;
+            let child = self.blossom_children[blossom][self.around(blossom, j)];
+            let p = self.end_before(blossom, j, flip) ^ flip;
+            if child >= self.len {
+                self.augment_blossom(child, self.endpoint[p]);
+            }
+            j += step;
+            let child = self.blossom_children[blossom][self.around(blossom, j)];
+            if child >= self.len {
+                self.augment_blossom(child, self.endpoint[p ^ 1]);
+            }
+            self.mate[self.endpoint[p]] = Some(p ^ 1);
+            self.mate[self.endpoint[p ^ 1]] = Some(p);
+        }
+        // rotate the cycle so the new base comes first
+        let first = self.around(blossom, start);
+        self.blossom_children[blossom].rotate_left(first);
+        self.blossom_ends[blossom].rotate_left(first);
+        self.blossom_base[blossom] = self.blossom_base[self.blossom_children[blossom][0]];
+    }
+
+    /// Augment the matching along the path through edge `k` between two outer blossoms back to both roots
+    fn augment_matching(&mut self, k: usize) {
+        let (v, w, _) = self.edges[k];
+        for (mut s, mut p) in [(v, 2 * k + 1), (w, 2 * k)] {
+            loop {
+                let bs = self.in_blossom[s];
+                if bs >= self.len {
+                    self.augment_blossom(bs, s);
+                }
+                self.mate[s] = Some(p);
+                let Some(end) = self.label_end[bs] else {
+                    break;
+                };
+                let bt = self.in_blossom[self.endpoint[end]];
+                let Some(end) = self.label_end[bt] else {
+                    break;
+                };
+                s = self.endpoint[end];
+                let j = self.endpoint[end ^ 1];
+                if bt >= self.len {
+                    self.augment_blossom(bt, j);
+                }
+                self.mate[j] = Some(end);
+                p = end ^ 1;
+            }
+        }
+    }
+
+    /// Change the dual variables by the largest amount that keeps them feasible, which makes a new edge usable or
+    /// lets an inner blossom be expanded.
+    ///
+    /// Returns `false` once no change can help, so the matching is complete.
+    fn adjust_duals(&mut self) -> bool {
+        let mut best: Option<(W, Step)> = None;
+        let mut consider = |delta: W, step: Step| {
+            if best.as_ref().is_none_or(|(best, _)| &delta < best) {
+                best = Some((delta, step));
+            }
+        };
+        for v in 0..self.len {
+            if self.label[self.in_blossom[v]] == FREE {
+                if let Some(k) = self.best_edge[v] {
+                    consider(self.slack(k), Step::Grow(k));
+                }
+            }
+        }
+        for b in 0..2 * self.len {
+            if self.blossom_parent[b].is_none() && self.label[b] == OUTER {
+                if let Some(k) = self.best_edge[b] {
+                    consider(self.slack(k) / W::from(2), Step::Close(k));
+                }
+            }
+        }
+        for b in self.len..2 * self.len {
+            if self.blossom_base[b].is_some()
+                && self.blossom_parent[b].is_none()
+                && self.label[b] == INNER
+            {
+                consider(self.dual[b].clone(), Step::Expand(b));
+            }
+        }
+        let Some((delta, step)) = best else {
+            return false;
+        };
+
+        for v in 0..self.len {
+            match self.label[self.in_blossom[v]] {
+                OUTER => self.dual[v] = self.dual[v].clone() - delta.clone(),
+                INNER => self.dual[v] = self.dual[v].clone() + delta.clone(),
+                _ => {}
+            }
+        }
+        for b in self.len..2 * self.len {
+            if self.blossom_base[b].is_some() && self.blossom_parent[b].is_none() {
+                match self.label[b] {
+                    OUTER => self.dual[b] = self.dual[b].clone() + delta.clone(),
+                    INNER => self.dual[b] = self.dual[b].clone() - delta.clone(),
+                    _ => {}
+                }
+            }
+        }
+
+        match step {
+            Step::Grow(k) => {
+                self.allowed[k] = true;
+                let (i, j, _) = self.edges[k];
+                let outer = if self.label[self.in_blossom[i]] == FREE {
+                    j
+                } else {
+                    i
+                };
+                self.queue.push(outer);
+            }
+            Step::Close(k) => {
+                self.allowed[k] = true;
+                self.queue.push(self.edges[k].0);
+            }
+            Step::Expand(b) => self.expand_blossom(b, false),
+        }
+        true
+    }
+}
+
+/// What a change of the dual variables makes possible
+enum Step {
+    /// An edge from an outer vertex to a free one becomes usable
+    Grow(usize),
+    /// An edge between two outer blossoms becomes usable
+    Close(usize),
+    /// An inner blossom's dual variable reaches zero, so it can be expanded
+    Expand(usize),
+}
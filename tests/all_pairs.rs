@@ -0,0 +1,61 @@
+use graph::Graph;
+
+/// A graph with negative edges but no negative cycles, since every weight is a nonnegative cost adjusted by a
+/// potential difference that cancels out around any cycle.
+fn make_graph() -> Graph<usize, i64> {
+    const NODES: usize = 25;
+    let mut graph = Graph::new();
+    let handles: Vec<_> = (0..NODES).map(|i| graph.insert(i).weak()).collect();
+    let potential = |i: usize| ((i * 37) % 11) as i64 * 3;
+    for i in 0..NODES {
+        for j in 0..NODES {
+            if i != j && (i * 7 + j * 13) % 6 == 0 {
+                let cost = ((i * 5 + j * 3) % 9) as i64;
+                graph.connect_weighted(handles[i], handles[j], cost + potential(i) - potential(j));
+            }
+        }
+    }
+    graph
+}
+
+#[test]
+pub fn test_all_pairs_match_bellman_ford() {
+    let graph = make_graph();
+    let floyd_warshall = graph.floyd_warshall().unwrap();
+    let johnson = graph.johnson().unwrap();
+    for start in 0..25 {
+        let start = graph.find(&start).unwrap();
+        let paths = graph.bellman_ford(start).unwrap();
+        for end in 0..25 {
+            let end = graph.find(&end).unwrap().weak();
+            let expected = paths.distance_to(end);
+            assert_eq!(floyd_warshall.distance(start.weak(), end), expected);
+            assert_eq!(johnson.distance(start.weak(), end), expected);
+            assert_eq!(&johnson[(start.weak(), end)], &expected.copied());
+            assert_eq!(
+                floyd_warshall
+                    .path(start.weak(), end)
+                    .map(|path| path.len()),
+                expected.copied()
+            );
+            assert_eq!(
+                johnson.path(start.weak(), end).map(|path| path.len()),
+                expected.copied()
+            );
+        }
+    }
+}
+
+#[test]
+pub fn test_all_pairs_negative_cycle() {
+    let mut graph = make_graph();
+    let a = graph.find(&3).unwrap().weak();
+    let b = graph.find(&4).unwrap().weak();
+    graph.connect_weighted(a, b, -100);
+    graph.connect_weighted(b, a, 1);
+
+    for cycle in [graph.floyd_warshall().err(), graph.johnson().err()] {
+        let cycle = cycle.unwrap();
+        assert!(cycle.cycle().len() < 0);
+    }
+}
@@ -0,0 +1,99 @@
+mod common;
+
+use common::Rng;
+use graph::{Graph, WeakNode};
+
+/// Two triangles sharing `C`, with a tail `D - F` hanging off `D` and an isolated `G`
+fn make_graph() -> Graph<char> {
+    let mut graph = Graph::new();
+    let nodes: Vec<_> = "ABCDEFG".chars().map(|c| graph.insert(c).weak()).collect();
+    for (a, b) in [(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 2), (3, 5)] {
+        graph.connect(nodes[a], nodes[b]);
+    }
+    graph
+}
+
+fn names(graph: &Graph<char>, nodes: impl IntoIterator<Item = WeakNode<char>>) -> String {
+    nodes
+        .into_iter()
+        .map(|node| *graph.weak_ref(node))
+        .collect()
+}
+
+#[test]
+pub fn test_articulation_points_and_bridges() {
+    let graph = make_graph();
+    assert_eq!(names(&graph, graph.articulation_points()), "CD");
+    let bridges: Vec<String> = graph
+        .bridges()
+        .into_iter()
+        .map(|(a, b)| names(&graph, [a, b]))
+        .collect();
+    assert_eq!(bridges, vec!["DF"]);
+
+    let mut components: Vec<String> = graph
+        .biconnected_components()
+        .into_iter()
+        .map(|component| names(&graph, component))
+        .collect();
+    components.sort();
+    assert_eq!(components, vec!["ABC", "CDE", "DF"]);
+}
+
+#[test]
+pub fn test_articulation_points_and_bridges_by_removal() {
+    let mut rng = Rng(0x1234_5678_9abc_def1);
+    for _ in 0..100 {
+        let len = 1 + rng.next(12);
+        let mut graph: Graph<usize> = Graph::new();
+        let nodes: Vec<_> = (0..len).map(|i| graph.insert(i).weak()).collect();
+        for _ in 0..rng.next(2 * len) {
+            graph.connect(nodes[rng.next(len)], nodes[rng.next(len)]);
+        }
+        let components = graph.weakly_connected_components().len();
+
+        let expected: Vec<_> = nodes
+            .iter()
+            .copied()
+            .filter(|&node| {
+                let mut without = graph.clone();
+                without.remove_node(without.translate(node));
+                without.weakly_connected_components().len() > components
+            })
+            .collect();
+        assert_eq!(graph.articulation_points(), expected);
+
+        let mut expected = Vec::new();
+        for &a in &nodes {
+            for &b in &nodes {
+                let mut without = graph.clone();
+                let (a_copy, b_copy) = (without.translate(a), without.translate(b));
+                let removed = without.disconnect(a_copy, b_copy).is_some()
+                    | without.disconnect(b_copy, a_copy).is_some();
+                if a < b && removed && without.weakly_connected_components().len() > components {
+                    expected.push((a, b));
+                }
+            }
+        }
+        assert_eq!(graph.bridges(), expected);
+
+        // components share at most one node, so every edge lies in the only component holding both its ends
+        let joined = |a: WeakNode<usize>, b: WeakNode<usize>| {
+            let points_to = |from, to| graph.weak_ref(from).neighbors().any(|n| n.weak() == to);
+            a < b && (points_to(a, b) || points_to(b, a))
+        };
+        let pairs = |members: &[WeakNode<usize>]| {
+            members
+                .iter()
+                .flat_map(|&a| members.iter().map(move |&b| (a, b)))
+                .filter(|&(a, b)| joined(a, b))
+                .count()
+        };
+        let covered: usize = graph
+            .biconnected_components()
+            .iter()
+            .map(|component| pairs(component))
+            .sum();
+        assert_eq!(covered, pairs(&nodes));
+    }
+}
@@ -0,0 +1,11 @@
+/// A small xorshift generator so the random graphs are reproducible without extra dependencies
+pub struct Rng(pub u64);
+
+impl Rng {
+    pub fn next(&mut self, bound: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % bound as u64) as usize
+    }
+}
@@ -0,0 +1,114 @@
+use graph::Graph;
+
+fn make_graph() -> Graph<char, u32> {
+    let mut graph = Graph::new();
+    let a = graph.insert('A').weak();
+    let b = graph.insert('B').weak();
+    let c = graph.insert('C').weak();
+    let d = graph.insert('D').weak();
+    let e = graph.insert('E').weak();
+    let f = graph.insert('F').weak();
+    // {A, B, C} -> {D, E} -> {F}
+    graph.connect_weighted(a, b, 1);
+    graph.connect_weighted(b, c, 1);
+    graph.connect_weighted(c, a, 1);
+    graph.connect_weighted(c, d, 2);
+    graph.connect_weighted(b, e, 3);
+    graph.connect_weighted(d, e, 1);
+    graph.connect_weighted(e, d, 1);
+    graph.connect_weighted(e, f, 4);
+    graph
+}
+
+#[test]
+pub fn test_strongly_connected_components() {
+    let graph = make_graph();
+    let components: Vec<Vec<char>> = graph
+        .strongly_connected_components()
+        .into_iter()
+        .map(|component| {
+            let mut members: Vec<char> =
+                component.into_iter().map(|n| *graph.weak_ref(n)).collect();
+            members.sort_unstable();
+            members
+        })
+        .collect();
+    assert_eq!(
+        components,
+        vec![vec!['F'], vec!['D', 'E'], vec!['A', 'B', 'C']]
+    );
+}
+
+#[test]
+pub fn test_condensation() {
+    let graph = make_graph();
+    let condensed = graph.condensation();
+    assert!(!condensed.is_cyclic());
+
+    let order: Vec<usize> = condensed
+        .toposort()
+        .unwrap()
+        .into_iter()
+        .map(|n| n.len())
+        .collect();
+    assert_eq!(order, vec![3, 2, 1]);
+    // both edges out of {A, B, C} lead to {D, E}, so they become a single edge
+    let top = condensed.toposort().unwrap()[0];
+    let next: Vec<usize> = top.neighbors().map(|n| n.len()).collect();
+    assert_eq!(next, vec![2]);
+    assert!(top.iter().all(|&member| *graph.weak_ref(member) < 'D'));
+}
+
+#[test]
+pub fn test_weak_connectivity() {
+    let mut graph: Graph<char> = Graph::new();
+    let a = graph.insert('A').weak();
+    let b = graph.insert('B').weak();
+    let c = graph.insert('C').weak();
+    let d = graph.insert('D').weak();
+    let e = graph.insert('E').weak();
+    graph.connect(b, a);
+    graph.connect(c, a);
+    graph.connect(d, e);
+
+    let components = graph.weakly_connected_components();
+    assert_eq!(components, vec![vec![a, b, c], vec![d, e]]);
+
+    let mut connectivity = graph.connectivity();
+    assert!(connectivity.same_component(b, c));
+    assert!(!connectivity.same_component(a, e));
+
+    let f = graph.insert('F').weak();
+    assert!(!connectivity.same_component(f, a));
+    assert!(connectivity.connect(f, a));
+    assert!(connectivity.connect(f, e));
+    assert!(!connectivity.connect(b, d));
+    assert!(connectivity.same_component(a, e));
+}
+
+#[test]
+pub fn test_connectivity_after_slot_reuse() {
+    let mut graph: Graph<char> = Graph::new();
+    let a = graph.insert('A').weak();
+    let b = graph.insert('B').weak();
+    let c = graph.insert('C').weak();
+    graph.connect(a, b);
+
+    let mut connectivity = graph.connectivity();
+    graph.remove_node(b);
+    let d = graph.insert('D').weak();
+    // D takes the slot B was in, but is a different node
+    assert!(!connectivity.same_component(a, d));
+    assert!(connectivity.same_component(d, d));
+    assert!(connectivity.connect(d, c));
+    assert!(connectivity.same_component(d, c));
+    assert!(!connectivity.same_component(a, d));
+    // once D is known, B is recognized as gone
+    assert!(!connectivity.same_component(a, b));
+    assert!(!connectivity.connect(b, c));
+    assert!(!connectivity.same_component(a, c));
+
+    // a node removed before the connectivity was built is never mistaken for a new one
+    let connectivity = graph.connectivity();
+    assert!(!connectivity.same_component(b, b));
+}
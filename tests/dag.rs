@@ -0,0 +1,47 @@
+use graph::Graph;
+
+#[test]
+pub fn test_toposort() {
+    let mut graph: Graph<&str> = Graph::new();
+    let fetch = graph.insert("fetch").weak();
+    let configure = graph.insert("configure").weak();
+    let compile = graph.insert("compile").weak();
+    let test = graph.insert("test").weak();
+    let package = graph.insert("package").weak();
+    graph.connect(compile, test);
+    graph.connect(compile, package);
+    graph.connect(configure, compile);
+    graph.connect(fetch, compile);
+    graph.connect(test, package);
+
+    assert!(!graph.is_cyclic());
+    let order: Vec<&str> = graph.toposort().unwrap().into_iter().map(|n| *n).collect();
+    let position = |task: &str| order.iter().position(|&t| t == task).unwrap();
+    assert_eq!(order.len(), 5);
+    assert!(position("fetch") < position("compile"));
+    assert!(position("configure") < position("compile"));
+    assert!(position("compile") < position("test"));
+    assert!(position("test") < position("package"));
+}
+
+#[test]
+pub fn test_toposort_cycle() {
+    let mut graph: Graph<char> = Graph::new();
+    let a = graph.insert('A').weak();
+    let b = graph.insert('B').weak();
+    let c = graph.insert('C').weak();
+    let d = graph.insert('D').weak();
+    graph.connect(a, b);
+    graph.connect(b, c);
+    graph.connect(c, d);
+    graph.connect(d, b);
+
+    assert!(graph.is_cyclic());
+    let cycle = graph.toposort().err().unwrap();
+    assert_eq!(format!("{:?}", cycle.cycle()), "['B', 'C', 'D', 'B']");
+
+    graph.disconnect(d, b);
+    graph.connect(d, d);
+    let cycle = graph.toposort().err().unwrap();
+    assert_eq!(format!("{cycle:?}"), "Cycle(['D', 'D'])");
+}
@@ -0,0 +1,64 @@
+use graph::Graph;
+
+#[test]
+pub fn test_node_edges() {
+    let mut graph: Graph<char, u32> = Graph::new();
+    let a = graph.insert('A').weak();
+    let b = graph.insert('B').weak();
+    let c = graph.insert('C').weak();
+    graph.connect_weighted(a, b, 4);
+    graph.connect_weighted(a, c, 7);
+    graph.connect_weighted(c, a, 1);
+
+    let edges: Vec<(char, char, u32)> = graph
+        .weak_ref(a)
+        .edges()
+        .map(|edge| (*edge.source, *edge.target, *edge.weight))
+        .collect();
+    assert_eq!(edges, vec![('A', 'B', 4), ('A', 'C', 7)]);
+    assert_eq!(graph.weak_ref(b).edges().count(), 0);
+}
+
+#[test]
+pub fn test_graph_edges() {
+    let mut graph: Graph<char, u32> = Graph::new();
+    let a = graph.insert('A').weak();
+    let b = graph.insert('B').weak();
+    let c = graph.insert('C').weak();
+    graph.connect_undirected_weighted(a, b, 2);
+    graph.connect_weighted(c, a, 5);
+    graph.remove_node(b);
+    let d = graph.insert('D').weak();
+    graph.connect_weighted(d, d, 3);
+
+    let edges: Vec<(char, char, u32)> = graph
+        .edges()
+        .map(|edge| (*edge.source, *edge.target, *edge.weight))
+        .collect();
+    // the slot of B is reused by D
+    assert_eq!(edges, vec![('D', 'D', 3), ('C', 'A', 5)]);
+}
+
+#[test]
+pub fn test_edge_weight() {
+    let mut graph: Graph<char, u32> = Graph::new();
+    let a = graph.insert('A').weak();
+    let b = graph.insert('B').weak();
+    graph.connect_undirected_weighted(a, b, 2);
+
+    assert_eq!(graph.edge_weight(a, b), Some(&2));
+    *graph.edge_weight_mut(a, b).unwrap() += 10;
+    assert_eq!(graph.edge_weight(a, b), Some(&12));
+    assert_eq!(graph.edge_weight(b, a), Some(&2));
+    assert_eq!(graph.edge_weight(a, a), None);
+    assert_eq!(
+        graph.weak_ref(a).edges().next().map(|edge| *edge.weight),
+        Some(12)
+    );
+
+    graph.remove_node(b);
+    assert_eq!(graph.edge_weight(a, b), None);
+    assert!(graph.edge_weight_mut(a, b).is_none());
+    let other: Graph<char, u32> = Graph::new();
+    assert_eq!(other.edge_weight(a, a), None);
+}
@@ -0,0 +1,60 @@
+use graph::Graph;
+
+#[test]
+pub fn test_nodes_and_counts() {
+    let mut graph: Graph<char, u32> = Graph::new();
+    assert!(graph.is_empty());
+    assert!(graph.arbitrary_node().is_none());
+    assert_eq!(graph.nodes().count(), 0);
+
+    let a = graph.insert('A').weak();
+    let b = graph.insert('B').weak();
+    let c = graph.insert('C').weak();
+    graph.connect_undirected_weighted(a, b, 1);
+    graph.connect_weighted(b, c, 2);
+    assert_eq!(graph.node_count(), 3);
+    assert_eq!(graph.edge_count(), 3);
+    assert!(!graph.is_empty());
+
+    graph.remove_node(a);
+    assert_eq!(graph.node_count(), 2);
+    assert_eq!(graph.edge_count(), 1);
+    let nodes: Vec<char> = graph.nodes().map(|n| *n).collect();
+    assert_eq!(nodes, vec!['B', 'C']);
+    assert_eq!(graph.arbitrary_node().map(|n| *n), Some('B'));
+
+    graph.remove_node(b);
+    graph.remove_node(c);
+    assert!(graph.is_empty());
+    assert!(graph.arbitrary_node().is_none());
+}
+
+#[test]
+pub fn test_iter_mut_and_index() {
+    let mut graph: Graph<String> = Graph::new();
+    let a = graph.insert("a".to_string()).weak();
+    let b = graph.insert("b".to_string()).weak();
+    let c = graph.insert("c".to_string()).weak();
+    graph.remove_node(b);
+
+    let mut seen = Vec::new();
+    for (node, value) in &mut graph {
+        value.push('!');
+        seen.push(node);
+    }
+    assert_eq!(seen, vec![a, c]);
+    assert_eq!(graph[a], "a!");
+
+    graph[c].push('?');
+    assert_eq!(graph[c], "c!?");
+    assert_eq!(*graph.weak_ref(c), "c!?");
+}
+
+#[test]
+#[should_panic]
+pub fn test_index_stale_node() {
+    let mut graph: Graph<char> = Graph::new();
+    let a = graph.insert('A').weak();
+    graph.remove_node(a);
+    let _ = graph[a];
+}
@@ -0,0 +1,61 @@
+use graph::{Graph, GraphError};
+
+#[test]
+pub fn test_try_connect() {
+    let mut graph: Graph<char, u32> = Graph::new();
+    let mut other: Graph<char, u32> = Graph::new();
+    let a = graph.insert('A').weak();
+    let b = graph.insert('B').weak();
+    let foreign = other.insert('X').weak();
+
+    assert_eq!(graph.try_connect_weighted(a, b, 1), Ok(()));
+    assert_eq!(
+        graph.try_connect_weighted(a, foreign, 1),
+        Err(GraphError::ForeignNode)
+    );
+    graph.remove_node(b);
+    assert_eq!(
+        graph.try_connect_undirected_weighted(a, b, 1),
+        Err(GraphError::StaleNode)
+    );
+    assert_eq!(graph.weak_ref(a).neighbors().count(), 0);
+}
+
+#[test]
+pub fn test_try_dijkstras() {
+    let mut graph: Graph<char, u32> = Graph::new();
+    let a = graph.insert('A').weak();
+    let b = graph.insert('B').weak();
+    graph.connect_weighted(a, b, 2);
+    let other = graph.clone();
+
+    let start = graph.weak_ref(a);
+    let foreign = other.weak_ref(other.translate(b));
+    assert_eq!(
+        graph.try_dijkstras(start, foreign).err(),
+        Some(GraphError::ForeignNode)
+    );
+    let path = graph.try_dijkstras(start, graph.weak_ref(b)).unwrap();
+    assert_eq!(path.map(|path| path.len()), Some(2));
+    assert!(graph
+        .try_dijkstras(graph.weak_ref(b), start)
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+pub fn test_try_push() {
+    let mut graph: Graph<char, u32> = Graph::new();
+    let a = graph.insert('A').weak();
+    let b = graph.insert('B').weak();
+    let c = graph.insert('C').weak();
+    graph.connect_weighted(a, b, 2);
+    graph.connect_weighted(b, c, 3);
+
+    let mut path = graph
+        .dijkstras(graph.weak_ref(a), graph.weak_ref(b))
+        .unwrap();
+    assert_eq!(path.try_push(a), Err(GraphError::MissingEdge));
+    assert_eq!(path.try_push(c), Ok(()));
+    assert_eq!(path.len(), 5);
+}
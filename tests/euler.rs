@@ -0,0 +1,278 @@
+mod common;
+
+use common::Rng;
+use std::collections::BTreeSet;
+
+use graph::{EulerError, Graph, Path, WeakNode};
+
+fn names(path: &Path<'_, char, ()>) -> String {
+    path.iter().map(|node| *node).collect()
+}
+
+#[test]
+pub fn test_eulerian_circuit() {
+    let mut graph: Graph<char> = Graph::new();
+    let nodes: Vec<_> = "ABCD".chars().map(|c| graph.insert(c).weak()).collect();
+    for (a, b) in [(0, 1), (1, 2), (2, 0), (0, 3), (3, 0)] {
+        graph.connect(nodes[a], nodes[b]);
+    }
+    let circuit = graph.eulerian_circuit(graph.weak_ref(nodes[1])).unwrap();
+    assert_eq!(names(&circuit), "BCADAB");
+    assert_eq!(names(&graph.eulerian_path().unwrap()), "ABCADA");
+
+    // one more edge out of C makes it the start of a path that ends at B
+    graph.connect(nodes[2], nodes[1]);
+    assert_eq!(
+        graph.eulerian_circuit(graph.weak_ref(nodes[0])).err(),
+        Some(EulerError::Unbalanced {
+            node: nodes[1],
+            incoming: 2,
+            outgoing: 1
+        })
+    );
+    let path = graph.eulerian_path().unwrap();
+    assert_eq!(names(&path), "CADABCB");
+
+    graph.connect(nodes[3], nodes[1]);
+    assert!(matches!(
+        graph.eulerian_path(),
+        Err(EulerError::Unbalanced { .. })
+    ));
+}
+
+#[test]
+pub fn test_eulerian_circuit_disconnected() {
+    let mut graph: Graph<char> = Graph::new();
+    let nodes: Vec<_> = "ABCDE".chars().map(|c| graph.insert(c).weak()).collect();
+    graph.connect(nodes[0], nodes[1]);
+    graph.connect(nodes[1], nodes[0]);
+    graph.connect(nodes[2], nodes[3]);
+    graph.connect(nodes[3], nodes[2]);
+    assert_eq!(
+        graph.eulerian_path().err(),
+        Some(EulerError::Disconnected { node: nodes[2] })
+    );
+    assert_eq!(
+        graph.eulerian_circuit(graph.weak_ref(nodes[4])).err(),
+        Some(EulerError::Disconnected { node: nodes[0] })
+    );
+
+    let empty: Graph<char> = Graph::new();
+    assert!(empty.eulerian_path().unwrap().iter().next().is_none());
+}
+
+#[test]
+pub fn test_undirected_eulerian_path() {
+    // the bridges of Königsberg, where every landmass has an odd number of bridges
+    let mut graph: Graph<char> = Graph::new();
+    let nodes: Vec<_> = "ABCD".chars().map(|c| graph.insert(c).weak()).collect();
+    for (a, b) in [(0, 1), (0, 2), (0, 3), (1, 3), (2, 3)] {
+        graph.connect_undirected(nodes[a], nodes[b]);
+    }
+    // the graph only keeps one edge per direction, so the doubled bridges become a path through D
+    assert_eq!(
+        graph
+            .undirected_eulerian_circuit(graph.weak_ref(nodes[0]))
+            .err(),
+        Some(EulerError::OddDegree {
+            node: nodes[0],
+            degree: 3
+        })
+    );
+    let path = graph.undirected_eulerian_path().unwrap();
+    assert_eq!(names(&path), "ABDACD");
+
+    // the third node of odd degree is the one reported
+    graph.connect_undirected(nodes[1], nodes[2]);
+    assert_eq!(
+        graph.undirected_eulerian_path().err(),
+        Some(EulerError::OddDegree {
+            node: nodes[2],
+            degree: 3
+        })
+    );
+
+    graph.connect(nodes[3], nodes[3]);
+    graph.connect(nodes[1], nodes[3]);
+    graph.disconnect(nodes[1], nodes[2]);
+    assert_eq!(
+        graph.undirected_eulerian_path().err(),
+        Some(EulerError::OneWay {
+            start: nodes[2],
+            end: nodes[1]
+        })
+    );
+}
+
+#[test]
+pub fn test_eulerian_random_walks() {
+    let mut rng = Rng(0x0bad_5eed_dead_beef);
+    for round in 0..100 {
+        // the edges of a random closed walk always form an Eulerian circuit
+        let len = 1 + rng.next(8);
+        let undirected = round % 2 == 1;
+        let mut graph: Graph<usize> = Graph::new();
+        let nodes: Vec<WeakNode<usize>> = (0..len).map(|i| graph.insert(i).weak()).collect();
+        let mut edges = BTreeSet::new();
+        let mut current = 0;
+        for step in 0..3 * len {
+            let target = if step + 1 == 3 * len {
+                0
+            } else {
+                rng.next(len)
+            };
+            let edge = if undirected {
+                (current.min(target), current.max(target))
+            } else {
+                (current, target)
+            };
+            if edges.insert(edge) {
+                if undirected {
+                    graph.connect_undirected(nodes[current], nodes[target]);
+                } else {
+                    graph.connect(nodes[current], nodes[target]);
+                }
+                current = target;
+            }
+        }
+        if current != 0 {
+            continue;
+        }
+
+        let start = graph.weak_ref(nodes[0]);
+        let circuit = if undirected {
+            graph.undirected_eulerian_circuit(start)
+        } else {
+            graph.eulerian_circuit(start)
+        }
+        .unwrap();
+        let walk: Vec<usize> = circuit.iter().map(|node| *node).collect();
+        assert_eq!(walk.first(), Some(&0));
+        assert_eq!(walk.last(), Some(&0));
+        let mut used = BTreeSet::new();
+        for pair in walk.windows(2) {
+            let edge = if undirected {
+                (pair[0].min(pair[1]), pair[0].max(pair[1]))
+            } else {
+                (pair[0], pair[1])
+            };
+            assert!(used.insert(edge));
+        }
+        assert_eq!(used, edges);
+    }
+}
+
+#[test]
+pub fn test_chinese_postman() {
+    // a square with one diagonal, so the ends of the diagonal have odd degree
+    let mut graph: Graph<char, i64> = Graph::new();
+    let nodes: Vec<_> = "ABCD".chars().map(|c| graph.insert(c).weak()).collect();
+    for (a, b, weight) in [(0, 1, 3), (1, 2, 1), (2, 3, 3), (3, 0, 1), (0, 2, 5)] {
+        graph.connect_undirected_weighted(nodes[a], nodes[b], weight);
+    }
+    let tour = graph.chinese_postman(graph.weak_ref(nodes[0])).unwrap();
+    // the cheapest way from A to C is through B or D at a cost of 4
+    assert_eq!(*tour.weight(), 13 + 4);
+    assert_eq!(tour.path().len(), 17);
+    let walk: Vec<char> = tour.path().iter().map(|node| *node).collect();
+    assert_eq!(walk.len(), 8);
+    assert_eq!(walk.first(), Some(&'A'));
+    assert_eq!(walk.last(), Some(&'A'));
+
+    graph.connect_weighted(nodes[1], nodes[3], 1);
+    assert_eq!(
+        graph.chinese_postman(graph.weak_ref(nodes[0])).err(),
+        Some(EulerError::OneWay {
+            start: nodes[1],
+            end: nodes[3]
+        })
+    );
+}
+
+/// Cheapest way to pair off the nodes in `unpaired`, given the distance between every pair
+fn cheapest_pairing(distance: &[Vec<Option<i64>>], unpaired: &[usize]) -> Option<i64> {
+    let Some((&first, rest)) = unpaired.split_first() else {
+        return Some(0);
+    };
+    (0..rest.len())
+        .filter_map(|i| {
+            let mut others = rest.to_vec();
+            let other = others.remove(i);
+            Some(distance[first][other]? + cheapest_pairing(distance, &others)?)
+        })
+        .min()
+}
+
+#[test]
+pub fn test_chinese_postman_random() {
+    let mut rng = Rng(0x5eed_cafe_f00d_1234);
+    for _ in 0..100 {
+        // a random tree keeps the graph connected, and the extra edges leave many nodes of odd degree
+        let len = 2 + rng.next(9);
+        let mut graph: Graph<usize, i64> = Graph::new();
+        let nodes: Vec<WeakNode<usize, i64>> = (0..len).map(|i| graph.insert(i).weak()).collect();
+        let mut distance = vec![vec![None; len]; len];
+        let mut total = 0;
+        let mut degree = vec![0; len];
+        let mut edges = Vec::new();
+        for b in 1..len {
+            edges.push((rng.next(b), b));
+        }
+        for _ in 0..rng.next(len + 1) {
+            edges.push((rng.next(len), rng.next(len)));
+        }
+        for (a, b) in edges {
+            if a == b || distance[a][b].is_some() {
+                continue;
+            }
+            let weight = 1 + rng.next(20) as i64;
+            graph.connect_undirected_weighted(nodes[a], nodes[b], weight);
+            distance[a][b] = Some(weight);
+            distance[b][a] = Some(weight);
+            total += weight;
+            degree[a] += 1;
+            degree[b] += 1;
+        }
+        for (i, row) in distance.iter_mut().enumerate() {
+            row[i] = Some(0);
+        }
+        for k in 0..len {
+            for i in 0..len {
+                for j in 0..len {
+                    if let (Some(a), Some(b)) = (distance[i][k], distance[k][j]) {
+                        if distance[i][j].is_none_or(|d| a + b < d) {
+                            distance[i][j] = Some(a + b);
+                        }
+                    }
+                }
+            }
+        }
+        let odd: Vec<usize> = (0..len).filter(|&i| degree[i] % 2 == 1).collect();
+        let expected = total + cheapest_pairing(&distance, &odd).unwrap();
+
+        let start = rng.next(len);
+        let tour = graph.chinese_postman(graph.weak_ref(nodes[start])).unwrap();
+        assert_eq!(*tour.weight(), expected);
+        assert_eq!(tour.path().len(), expected);
+        let walk: Vec<usize> = tour.path().iter().map(|node| *node).collect();
+        assert_eq!(walk.first(), Some(&start));
+        assert_eq!(walk.last(), Some(&start));
+        let covered: BTreeSet<(usize, usize)> = walk
+            .windows(2)
+            .map(|pair| (pair[0].min(pair[1]), pair[0].max(pair[1])))
+            .collect();
+        assert_eq!(
+            covered.len() as i64,
+            (total != 0) as i64 * covered.len() as i64
+        );
+        for a in 0..len {
+            for b in a + 1..len {
+                let joined = graph
+                    .weak_ref(nodes[a])
+                    .neighbors()
+                    .any(|n| n.weak() == nodes[b]);
+                assert_eq!(covered.contains(&(a, b)), joined);
+            }
+        }
+    }
+}
@@ -0,0 +1,217 @@
+mod common;
+
+use common::Rng;
+use graph::{Graph, MultiGraph, WeakNode};
+
+const NAMES: [&str; 6] = ["s", "v1", "v2", "v3", "v4", "t"];
+
+fn make_graph() -> Graph<&'static str, u64> {
+    let mut graph = Graph::new();
+    let nodes: Vec<_> = NAMES
+        .into_iter()
+        .map(|name| graph.insert(name).weak())
+        .collect();
+    for (start, end, capacity) in [
+        (0, 1, 16),
+        (0, 2, 13),
+        (2, 1, 4),
+        (1, 3, 12),
+        (3, 2, 9),
+        (2, 4, 14),
+        (4, 3, 7),
+        (3, 5, 20),
+        (4, 5, 4),
+    ] {
+        graph.connect_weighted(nodes[start], nodes[end], capacity);
+    }
+    graph
+}
+
+fn capacity(
+    graph: &Graph<&'static str, u64>,
+    start: WeakNode<&str, u64>,
+    end: WeakNode<&str, u64>,
+) -> u64 {
+    let mut edge = graph
+        .dijkstras(graph.weak_ref(start), graph.weak_ref(start))
+        .unwrap();
+    edge.push(end);
+    edge.len()
+}
+
+#[test]
+pub fn test_max_flow() {
+    let graph = make_graph();
+    let source = graph.find(&"s").unwrap();
+    let sink = graph.find(&"t").unwrap();
+    let position = |node| {
+        NAMES
+            .iter()
+            .position(|name| name == &*graph.weak_ref(node))
+            .unwrap()
+    };
+
+    for flow in [
+        graph.max_flow(source, sink),
+        graph.max_flow_edmonds_karp(source, sink),
+    ] {
+        assert_eq!(*flow.value(), 23);
+
+        // flow is conserved everywhere except the source and sink, and never exceeds capacity
+        let mut balance = [0; 6];
+        for (start, end, &amount) in flow.edge_flows() {
+            assert!(amount <= capacity(&graph, start, end));
+            balance[position(start)] -= amount as i64;
+            balance[position(end)] += amount as i64;
+        }
+        assert_eq!(balance, [-23, 0, 0, 0, 0, 23]);
+
+        // the edges leaving the source side of the cut are saturated
+        let cut = flow.min_cut();
+        assert!(cut.contains(&source.weak()));
+        assert!(!cut.contains(&sink.weak()));
+        let mut cut_capacity = 0;
+        for &start in &cut {
+            for end in graph.weak_ref(start).neighbors() {
+                if !cut.contains(&end.weak()) {
+                    assert_eq!(
+                        flow.flow(start, end.weak()),
+                        Some(&capacity(&graph, start, end.weak()))
+                    );
+                    cut_capacity += capacity(&graph, start, end.weak());
+                }
+            }
+        }
+        assert_eq!(cut_capacity, 23);
+    }
+}
+
+#[test]
+pub fn test_max_flow_algorithms_agree() {
+    let mut rng = Rng(0x853c_49e6_748f_ea9b);
+    for _ in 0..20 {
+        let mut graph: Graph<u64, u64> = Graph::new();
+        let nodes: Vec<_> = (0..30).map(|i| graph.insert(i).weak()).collect();
+        for _ in 0..120 {
+            let start = nodes[rng.next(30)];
+            let end = nodes[rng.next(30)];
+            graph.connect_weighted(start, end, rng.next(50) as u64);
+        }
+        let source = graph.weak_ref(nodes[0]);
+        let sink = graph.weak_ref(nodes[29]);
+        assert_eq!(
+            graph.max_flow(source, sink).value(),
+            graph.max_flow_edmonds_karp(source, sink).value()
+        );
+    }
+}
+
+fn make_costed_graph() -> Graph<char, (i64, i64)> {
+    let mut graph = Graph::new();
+    let nodes: Vec<_> = "sabt".chars().map(|c| graph.insert(c).weak()).collect();
+    for (start, end, capacity, cost) in [
+        (0, 1, 3, 1),
+        (0, 2, 2, 5),
+        (1, 2, 2, 1),
+        (1, 3, 2, 6),
+        (2, 3, 3, 1),
+    ] {
+        graph.connect_weighted(nodes[start], nodes[end], (capacity, cost));
+    }
+    graph
+}
+
+#[test]
+pub fn test_min_cost_flow() {
+    let graph = make_costed_graph();
+    let source = graph.find(&'s').unwrap();
+    let sink = graph.find(&'t').unwrap();
+    let a = graph.find(&'a').unwrap().weak();
+    let b = graph.find(&'b').unwrap().weak();
+
+    let flow = graph.min_cost_flow(source, sink, 4).unwrap();
+    assert_eq!((*flow.value(), *flow.cost()), (4, 19));
+    assert_eq!(flow.flow(a, b), Some(&2));
+    assert_eq!(flow.flow(b, sink.weak()), Some(&3));
+    assert_eq!(flow.flow(a, sink.weak()), Some(&1));
+    assert_eq!(flow.flow(source.weak(), b), Some(&1));
+
+    // asking for more than the network can carry gives the cheapest maximum flow
+    let flow = graph.min_cost_flow(source, sink, 10).unwrap();
+    assert_eq!((*flow.value(), *flow.cost()), (5, 29));
+}
+
+#[test]
+pub fn test_min_cost_flow_negative_costs() {
+    let mut graph = make_costed_graph();
+    let a = graph.find(&'a').unwrap().weak();
+    let b = graph.find(&'b').unwrap().weak();
+    graph.connect_weighted(a, b, (2, -2));
+    let flow = graph
+        .min_cost_flow(graph.find(&'s').unwrap(), graph.find(&'t').unwrap(), 2)
+        .unwrap();
+    assert_eq!((*flow.value(), *flow.cost()), (2, 0));
+
+    graph.connect_weighted(b, a, (1, 1));
+    let cycle = graph
+        .min_cost_flow(graph.find(&'s').unwrap(), graph.find(&'t').unwrap(), 2)
+        .err()
+        .unwrap();
+    assert_eq!(cycle.cycle().iter().count(), 3);
+}
+
+#[test]
+pub fn test_flow_on_parallel_edges() {
+    let mut graph: MultiGraph<char, u32> = MultiGraph::new();
+    let s = graph.insert('s').weak();
+    let a = graph.insert('a').weak();
+    let t = graph.insert('t').weak();
+    let narrow = graph.connect_weighted(s, a, 3);
+    let wide = graph.connect_weighted(s, a, 4);
+    graph.connect_weighted(a, t, 10);
+
+    let flow = graph.max_flow(graph.weak_ref(s), graph.weak_ref(t));
+    assert_eq!(*flow.value(), 7);
+    assert_eq!(flow.flow_on(narrow), Some(&3));
+    assert_eq!(flow.flow_on(wide), Some(&4));
+    assert_eq!(flow.flow(s, a), Some(&3));
+    assert_eq!(flow.edge_flows().count(), 3);
+
+    let mut graph: MultiGraph<char, (i32, i32)> = MultiGraph::new();
+    let s = graph.insert('s').weak();
+    let t = graph.insert('t').weak();
+    let dear = graph.connect_weighted(s, t, (2, 5));
+    let cheap = graph.connect_weighted(s, t, (2, 1));
+    let flow = graph
+        .min_cost_flow(graph.weak_ref(s), graph.weak_ref(t), 3)
+        .unwrap();
+    assert_eq!((*flow.value(), *flow.cost()), (3, 7));
+    assert_eq!(flow.flow_on(cheap), Some(&2));
+    assert_eq!(flow.flow_on(dear), Some(&1));
+}
+
+#[test]
+pub fn test_min_cost_flow_unsigned() {
+    let mut graph: Graph<char, (u64, u64)> = Graph::new();
+    let s = graph.insert('s').weak();
+    let a = graph.insert('a').weak();
+    let b = graph.insert('b').weak();
+    let t = graph.insert('t').weak();
+    graph.connect_weighted(s, t, (3, 2));
+    let flow = graph
+        .min_cost_flow(graph.weak_ref(s), graph.weak_ref(t), 2)
+        .unwrap();
+    assert_eq!((*flow.value(), *flow.cost()), (2, 4));
+
+    // the cheapest second path undoes flow along the middle edge, which gives back its cost
+    graph.connect_weighted(s, a, (1, 1));
+    graph.connect_weighted(a, b, (1, 1));
+    graph.connect_weighted(b, t, (1, 1));
+    graph.connect_weighted(s, b, (1, 5));
+    graph.connect_weighted(a, t, (1, 5));
+    let flow = graph
+        .min_cost_flow(graph.weak_ref(s), graph.weak_ref(t), 5)
+        .unwrap();
+    assert_eq!((*flow.value(), *flow.cost()), (5, 3 * 2 + 1 + 5 + 5 + 1));
+    assert_eq!(flow.flow(a, b), Some(&0));
+}
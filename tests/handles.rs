@@ -0,0 +1,55 @@
+use graph::{Graph, GraphError};
+
+#[test]
+pub fn test_foreign_handle() {
+    let mut first: Graph<char> = Graph::new();
+    let mut second: Graph<char> = Graph::new();
+    let a = first.insert('A').weak();
+    second.insert('B');
+
+    assert_eq!(second.try_weak_ref(a).err(), Some(GraphError::ForeignNode));
+    assert_eq!(second.remove_node(a), None);
+    assert_eq!(
+        *second.weak_ref(second.arbitrary_node().unwrap().weak()),
+        'B'
+    );
+}
+
+#[test]
+pub fn test_clone_handle() {
+    let mut graph: Graph<char> = Graph::new();
+    let a = graph.insert('A').weak();
+    let mut copy = graph.clone();
+    let x = graph.insert('X').weak();
+    let y = copy.insert('Y').weak();
+
+    // a clone has its own identity, so handles from the two graphs never alias
+    assert_eq!(copy.try_weak_ref(x).err(), Some(GraphError::ForeignNode));
+    assert_eq!(graph.try_weak_ref(y).err(), Some(GraphError::ForeignNode));
+    assert_eq!(copy.try_weak_ref(a).err(), Some(GraphError::ForeignNode));
+
+    // nodes copied by the clone can be translated in either direction
+    assert_eq!(*copy.weak_ref(copy.translate(a)), 'A');
+    assert_eq!(graph.translate(copy.translate(a)), a);
+    assert_eq!(copy.try_translate(x).err(), Some(GraphError::ForeignNode));
+    assert_eq!(graph.try_translate(y).err(), Some(GraphError::ForeignNode));
+    copy.remove_node(copy.translate(a));
+    assert_eq!(copy.try_translate(a).err(), Some(GraphError::StaleNode));
+}
+
+#[test]
+pub fn test_stale_handle() {
+    let mut graph: Graph<char> = Graph::new();
+    let a = graph.insert('A').weak();
+    let b = graph.insert('B').weak();
+    graph.connect(b, a);
+
+    graph.remove_node(a);
+    let c = graph.insert('C').weak();
+
+    assert_ne!(a, c);
+    assert_eq!(graph.try_weak_ref(a).err(), Some(GraphError::StaleNode));
+    assert_eq!(graph.try_weak_mut(a).err(), Some(GraphError::StaleNode));
+    assert_eq!(*graph.weak_ref(c), 'C');
+    assert_eq!(graph.weak_ref(b).neighbors().count(), 0);
+}
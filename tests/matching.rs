@@ -0,0 +1,208 @@
+mod common;
+
+use common::Rng;
+use graph::{AssignmentError, Graph, GraphError};
+
+#[test]
+pub fn test_is_bipartite() {
+    let mut graph: Graph<&str> = Graph::new();
+    let alice = graph.insert("alice").weak();
+    let bob = graph.insert("bob").weak();
+    let morning = graph.insert("morning").weak();
+    let evening = graph.insert("evening").weak();
+    graph.connect_undirected(alice, morning);
+    graph.connect_undirected(bob, morning);
+    graph.connect(evening, bob);
+
+    let (left, right) = graph.is_bipartite().unwrap();
+    assert_eq!(left, vec![alice, bob]);
+    assert_eq!(right, vec![morning, evening]);
+
+    // a triangle can't be split
+    graph.connect_undirected(alice, bob);
+    let cycle = graph.is_bipartite().err().unwrap();
+    let nodes: Vec<&str> = cycle.nodes().iter().map(|n| **n).collect();
+    assert_eq!(nodes.len(), 4);
+    assert_eq!(nodes.first(), nodes.last());
+    assert!(graph.maximum_bipartite_matching().is_err());
+
+    // the cycle may step against the direction of an edge
+    let mut graph: Graph<char> = Graph::new();
+    let a = graph.insert('A').weak();
+    let b = graph.insert('B').weak();
+    let c = graph.insert('C').weak();
+    graph.connect(a, b);
+    graph.connect(b, c);
+    graph.connect(a, c);
+    let cycle = graph.is_bipartite().err().unwrap();
+    assert_eq!(format!("{cycle:?}"), "OddCycle(['A', 'B', 'C', 'A'])");
+}
+
+/// Simple augmenting path matching to check Hopcroft-Karp against
+fn kuhn(adjacency: &[Vec<usize>], right: usize) -> usize {
+    fn augment(
+        l: usize,
+        adjacency: &[Vec<usize>],
+        seen: &mut [bool],
+        mate: &mut [Option<usize>],
+    ) -> bool {
+        for &r in &adjacency[l] {
+            if !seen[r] {
+                seen[r] = true;
+                if mate[r].is_none_or(|other| augment(other, adjacency, seen, mate)) {
+                    mate[r] = Some(l);
+                    return true;
+                }
+            }
+        }
+        false
+    }
+    let mut mate = vec![None; right];
+    (0..adjacency.len())
+        .filter(|&l| augment(l, adjacency, &mut vec![false; right], &mut mate))
+        .count()
+}
+
+#[test]
+pub fn test_maximum_bipartite_matching() {
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+    for _ in 0..20 {
+        let mut graph: Graph<(bool, usize)> = Graph::new();
+        let workers: Vec<_> = (0..15).map(|i| graph.insert((false, i)).weak()).collect();
+        let shifts: Vec<_> = (0..12).map(|i| graph.insert((true, i)).weak()).collect();
+        let mut adjacency = vec![Vec::new(); 15];
+        for _ in 0..30 {
+            let (w, s) = (rng.next(15), rng.next(12));
+            graph.connect(workers[w], shifts[s]);
+            adjacency[w].push(s);
+        }
+
+        let matching = graph.maximum_bipartite_matching().unwrap();
+        assert_eq!(matching.len(), kuhn(&adjacency, 12));
+        let mut used = Vec::new();
+        for (a, b) in matching {
+            let (a, b) = (*graph.weak_ref(a), *graph.weak_ref(b));
+            // each pair is an edge, and no node is matched twice
+            let (worker, shift) = if a.0 { (b.1, a.1) } else { (a.1, b.1) };
+            assert!(adjacency[worker].contains(&shift));
+            assert!(!used.contains(&a) && !used.contains(&b));
+            used.extend([a, b]);
+        }
+    }
+}
+
+#[test]
+pub fn test_assignment() {
+    let costs = [[9, 2, 7, 8], [6, 4, 3, 7], [5, 8, 1, 8], [7, 6, 9, 4]];
+    let mut graph: Graph<&str, i64> = Graph::new();
+    let workers: Vec<_> = ["a", "b", "c", "d"]
+        .map(|name| graph.insert(name).weak())
+        .into();
+    let jobs: Vec<_> = ["w", "x", "y", "z"]
+        .map(|name| graph.insert(name).weak())
+        .into();
+    for (i, row) in costs.iter().enumerate() {
+        for (j, &cost) in row.iter().enumerate() {
+            graph.connect_weighted(workers[i], jobs[j], cost);
+        }
+    }
+
+    let assignment = graph.assignment(&workers, &jobs).unwrap();
+    assert_eq!(*assignment.cost(), 13);
+    let pairs: Vec<(&str, &str)> = assignment
+        .pairs()
+        .iter()
+        .map(|&(worker, job)| (*graph.weak_ref(worker), *graph.weak_ref(job)))
+        .collect();
+    assert_eq!(pairs, vec![("a", "x"), ("b", "w"), ("c", "y"), ("d", "z")]);
+
+    // edges pointing from the right side count too
+    let reversed = graph.assignment(&jobs, &workers).unwrap();
+    assert_eq!(*reversed.cost(), 13);
+}
+
+#[test]
+pub fn test_assignment_errors() {
+    let mut graph: Graph<char, i64> = Graph::new();
+    let a = graph.insert('A').weak();
+    let b = graph.insert('B').weak();
+    let x = graph.insert('X').weak();
+    let y = graph.insert('Y').weak();
+    graph.connect_weighted(a, x, 1);
+    graph.connect_weighted(b, x, 2);
+
+    assert_eq!(
+        graph.assignment(&[a, b], &[x]).err(),
+        Some(AssignmentError::Unbalanced { left: 2, right: 1 })
+    );
+    assert_eq!(
+        graph.assignment(&[a, b], &[x, y]).err(),
+        Some(AssignmentError::NoPerfectMatching)
+    );
+    graph.connect_weighted(a, y, -5);
+    assert_eq!(
+        graph.assignment(&[a, b], &[x, y]).map(|a| *a.cost()),
+        Ok(-3)
+    );
+    graph.remove_node(y);
+    assert_eq!(
+        graph.assignment(&[a, b], &[x, y]).err(),
+        Some(AssignmentError::Node(GraphError::StaleNode))
+    );
+}
+
+/// Size of a maximum matching by trying every way to pair off the lowest node
+fn brute_force_matching(adjacency: &[Vec<bool>], unused: u32) -> usize {
+    let Some(first) = (0..adjacency.len()).find(|&i| unused & (1 << i) != 0) else {
+        return 0;
+    };
+    let rest = unused & !(1 << first);
+    let mut best = brute_force_matching(adjacency, rest);
+    for other in 0..adjacency.len() {
+        if rest & (1 << other) != 0 && adjacency[first][other] {
+            best = best.max(1 + brute_force_matching(adjacency, rest & !(1 << other)));
+        }
+    }
+    best
+}
+
+#[test]
+pub fn test_maximum_matching() {
+    // a path leading into a pentagon, where the augmenting path has to go around the odd cycle
+    let mut graph: Graph<usize> = Graph::new();
+    let nodes: Vec<_> = (0..7).map(|i| graph.insert(i).weak()).collect();
+    for (a, b) in [(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 6), (6, 2)] {
+        graph.connect(nodes[a], nodes[b]);
+    }
+    let matching = graph.maximum_matching();
+    assert_eq!(matching.len(), 3);
+    assert!(graph.maximum_bipartite_matching().is_err());
+
+    let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+    for _ in 0..200 {
+        let len = 1 + rng.next(12);
+        let mut graph: Graph<usize> = Graph::new();
+        let nodes: Vec<_> = (0..len).map(|i| graph.insert(i).weak()).collect();
+        let mut adjacency = vec![vec![false; len]; len];
+        for _ in 0..rng.next(2 * len + 1) {
+            let (a, b) = (rng.next(len), rng.next(len));
+            graph.connect(nodes[a], nodes[b]);
+            adjacency[a][b] = a != b;
+            adjacency[b][a] = a != b;
+        }
+
+        let matching = graph.maximum_matching();
+        assert_eq!(
+            matching.len(),
+            brute_force_matching(&adjacency, (1 << len) - 1)
+        );
+        let mut used = vec![false; len];
+        for &(a, b) in &matching {
+            let (a, b) = (*graph.weak_ref(a), *graph.weak_ref(b));
+            assert!(a < b && adjacency[a][b]);
+            assert!(!used[a] && !used[b]);
+            used[a] = true;
+            used[b] = true;
+        }
+    }
+}
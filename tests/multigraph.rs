@@ -0,0 +1,228 @@
+mod common;
+
+use common::Rng;
+use graph::{EulerError, Graph, Multi, MultiGraph};
+
+#[test]
+pub fn test_parallel_edges() {
+    let mut graph: MultiGraph<char, u32> = MultiGraph::new();
+    let a = graph.insert('A').weak();
+    let b = graph.insert('B').weak();
+    let slow = graph.connect_weighted(a, b, 7);
+    let fast = graph.connect_weighted(a, b, 3);
+    let back = graph.connect_weighted(b, a, 5);
+
+    assert_ne!(slow, fast);
+    assert_eq!((fast.start(), fast.end()), (a, b));
+    assert_eq!(graph.weight(slow), Some(&7));
+    assert_eq!(graph.weight(fast), Some(&3));
+    assert_eq!(graph.edge_weight(a, b), Some(&7));
+    assert_eq!(graph.edge_count(), 3);
+    assert_eq!(graph.weak_ref(a).out_degree(), 2);
+    assert_eq!(graph.weak_ref(b).in_degree(), 2);
+
+    let weights: Vec<u32> = graph
+        .edges_between(a, b)
+        .into_iter()
+        .map(|edge| *edge.weight)
+        .collect();
+    assert_eq!(weights, vec![7, 3]);
+    let handles: Vec<_> = graph.weak_ref(a).edges().map(|edge| edge.weak()).collect();
+    assert_eq!(handles, vec![slow, fast]);
+
+    *graph.weight_mut(slow).unwrap() = 1;
+    assert_eq!(graph.remove_edge(fast), Some(3));
+    assert_eq!(graph.remove_edge(fast), None);
+    assert_eq!(graph.weight(fast), None);
+    assert_eq!(graph.weight(slow), Some(&1));
+    assert_eq!(graph.weight(back), Some(&5));
+}
+
+#[test]
+pub fn test_dijkstras_takes_cheapest_edge() {
+    let mut graph: MultiGraph<char, u32> = MultiGraph::new();
+    let nodes: Vec<_> = "ABC".chars().map(|c| graph.insert(c).weak()).collect();
+    graph.connect_weighted(nodes[0], nodes[1], 9);
+    graph.connect_weighted(nodes[0], nodes[1], 2);
+    graph.connect_weighted(nodes[0], nodes[1], 4);
+    graph.connect_weighted(nodes[1], nodes[2], 6);
+    graph.connect_weighted(nodes[1], nodes[2], 1);
+    graph.connect_weighted(nodes[0], nodes[2], 5);
+
+    let path = graph
+        .dijkstras(graph.weak_ref(nodes[0]), graph.weak_ref(nodes[2]))
+        .unwrap();
+    assert_eq!(path.iter().map(|n| *n).collect::<String>(), "ABC");
+    assert_eq!(path.len(), 3);
+}
+
+#[test]
+pub fn test_disconnect_and_remove_node() {
+    let mut graph: MultiGraph<char> = MultiGraph::new();
+    let a = graph.insert('A').weak();
+    let b = graph.insert('B').weak();
+    let c = graph.insert('C').weak();
+    graph.connect(a, b);
+    graph.connect(a, b);
+    let loop_edge = graph.connect(c, c);
+    let to_c = graph.connect(b, c);
+    graph.connect(b, c);
+
+    assert_eq!(graph.disconnect(a, b), vec![(), ()]);
+    assert!(graph.disconnect(a, b).is_empty());
+    assert_eq!(graph.edge_count(), 3);
+
+    assert_eq!(graph.remove_node(c), Some('C'));
+    assert_eq!(graph.weight(to_c), None);
+    assert_eq!(graph.weight(loop_edge), None);
+    assert_eq!(graph.edge_count(), 0);
+    assert_eq!(graph.weak_ref(b).out_degree(), 0);
+}
+
+#[test]
+pub fn test_matches_cheapest_simple_graph() {
+    let mut rng = Rng(0x1357_2468_9bdf_ace0);
+    for _ in 0..50 {
+        let mut multi: MultiGraph<usize, u32> = MultiGraph::new();
+        let mut simple: Graph<usize, u32> = Graph::new();
+        let len = 2 + rng.next(8);
+        let nodes: Vec<_> = (0..len).map(|i| multi.insert(i).weak()).collect();
+        let cheapest: Vec<_> = (0..len).map(|i| simple.insert(i).weak()).collect();
+        for _ in 0..rng.next(30) {
+            let (start, end) = (rng.next(len), rng.next(len));
+            let weight = 1 + rng.next(9) as u32;
+            multi.connect_weighted(nodes[start], nodes[end], weight);
+            let best = simple
+                .edge_weight(cheapest[start], cheapest[end])
+                .map_or(weight, |&old| old.min(weight));
+            simple.connect_weighted(cheapest[start], cheapest[end], best);
+        }
+
+        for i in 0..len {
+            for j in 0..len {
+                let expected = simple
+                    .dijkstras(simple.weak_ref(cheapest[i]), simple.weak_ref(cheapest[j]))
+                    .map(|path| path.len());
+                let actual = multi
+                    .dijkstras(multi.weak_ref(nodes[i]), multi.weak_ref(nodes[j]))
+                    .map(|path| path.len());
+                assert_eq!(actual, expected);
+            }
+        }
+    }
+}
+
+#[test]
+pub fn test_translate_edge() {
+    let mut graph: MultiGraph<char, u32> = MultiGraph::new();
+    let a = graph.insert('A').weak();
+    let b = graph.insert('B').weak();
+    graph.connect_weighted(a, b, 1);
+    let second = graph.connect_weighted(a, b, 2);
+    let mut copy = graph.clone();
+
+    assert_eq!(copy.weight(second), None);
+    let translated = copy.translate_edge(second).unwrap();
+    assert_eq!(copy.weight(translated), Some(&2));
+    assert_eq!(graph.translate_edge(translated), Some(second));
+    copy.remove_edge(translated);
+    assert_eq!(copy.translate_edge(second), None);
+}
+
+#[test]
+pub fn test_path_len_follows_taken_edges() {
+    let mut graph: MultiGraph<char, f64> = MultiGraph::new();
+    let a = graph.insert('A').weak();
+    let b = graph.insert('B').weak();
+    graph.connect_weighted(a, b, 1.5);
+    graph.connect_weighted(b, a, 1.0);
+    graph.connect_weighted(a, b, 2.5);
+    graph.connect_weighted(b, a, 0.5);
+
+    // every parallel edge is walked once, so each adds its own weight
+    let circuit = graph.eulerian_circuit(graph.weak_ref(a)).unwrap();
+    assert_eq!(circuit.iter().map(|n| *n).collect::<String>(), "ABABA");
+    assert!((circuit.len() - 5.5).abs() < f64::EPSILON);
+
+    let mut path = graph.eulerian_path().unwrap();
+    path.push(b);
+    assert!((path.len() - 7.0).abs() < f64::EPSILON);
+}
+
+#[test]
+pub fn test_parallel_edges_ignoring_direction() {
+    let mut graph: MultiGraph<char> = MultiGraph::new();
+    let a = graph.insert('A').weak();
+    let b = graph.insert('B').weak();
+    let c = graph.insert('C').weak();
+    graph.connect(a, b);
+    graph.connect(b, a);
+    graph.connect(a, b);
+    graph.connect(b, a);
+
+    // two pairs are two undirected edges, so every degree is even
+    let circuit = graph
+        .undirected_eulerian_circuit(graph.weak_ref(a))
+        .unwrap();
+    assert_eq!(circuit.iter().map(|n| *n).collect::<String>(), "ABA");
+    graph.connect(a, b);
+    graph.connect(b, a);
+    graph.connect(a, b);
+    graph.connect(b, a);
+    let circuit = graph
+        .undirected_eulerian_circuit(graph.weak_ref(a))
+        .unwrap();
+    assert_eq!(circuit.iter().map(|n| *n).collect::<String>(), "ABABA");
+
+    // a doubled edge stays connected after losing either copy, so it is no bridge
+    graph.connect(b, c);
+    graph.connect(b, c);
+    assert!(graph.bridges().is_empty());
+    assert!(graph.articulation_points().contains(&b));
+    assert_eq!(graph.biconnected_components().len(), 2);
+
+    let mut graph: MultiGraph<char> = MultiGraph::new();
+    let a = graph.insert('A').weak();
+    let b = graph.insert('B').weak();
+    graph.connect(a, b);
+    graph.connect(a, b);
+    graph.connect(b, a);
+    assert_eq!(
+        graph.undirected_eulerian_path().err(),
+        Some(EulerError::OneWay { start: a, end: b })
+    );
+    assert_eq!(graph.bridges(), vec![]);
+}
+
+#[test]
+pub fn test_spanning_tree_keeps_kind() {
+    let mut graph: MultiGraph<char, u32> = MultiGraph::new();
+    let a = graph.insert('A').weak();
+    let b = graph.insert('B').weak();
+    let c = graph.insert('C').weak();
+    graph.connect_weighted(a, b, 7);
+    graph.connect_weighted(a, b, 3);
+    graph.connect_weighted(b, c, 5);
+
+    for mut tree in [
+        graph.minimum_spanning_tree(),
+        graph.minimum_spanning_tree_prim(),
+    ] {
+        let (x, y) = (tree.translate(a), tree.translate(b));
+        let weights: Vec<u32> = tree
+            .edges_between(x, y)
+            .iter()
+            .map(|edge| *edge.weight)
+            .collect();
+        assert_eq!(weights, vec![3]);
+
+        // the forest is still a multigraph, so further connections add edges
+        tree.connect_weighted(x, y, 1);
+        tree.connect_weighted(x, y, 2);
+        assert_eq!(tree.edges_between(x, y).len(), 3);
+    }
+
+    // as is a clone of the graph behind `Deref`, which says so in its type
+    let inner: Graph<char, u32, Multi> = (*graph).clone();
+    assert_eq!(inner.edge_count(), 3);
+}
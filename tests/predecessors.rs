@@ -0,0 +1,70 @@
+mod common;
+
+use common::Rng;
+use graph::{Graph, WeakNode};
+
+#[test]
+pub fn test_predecessors() {
+    let mut graph: Graph<char, u32> = Graph::new();
+    let a = graph.insert('A').weak();
+    let b = graph.insert('B').weak();
+    let c = graph.insert('C').weak();
+    graph.connect_weighted(a, c, 1);
+    graph.connect_undirected_weighted(b, c, 2);
+    graph.connect_weighted(c, c, 3);
+
+    let predecessors: Vec<char> = graph.weak_ref(c).predecessors().map(|n| *n).collect();
+    assert_eq!(predecessors, vec!['A', 'B', 'C']);
+    assert_eq!(graph.weak_ref(c).in_degree(), 3);
+    assert_eq!(graph.weak_ref(c).out_degree(), 2);
+    assert_eq!(graph.weak_ref(a).in_degree(), 0);
+    assert_eq!(graph.weak_ref(a).out_degree(), 1);
+
+    graph.disconnect(b, c);
+    let predecessors: Vec<char> = graph.weak_ref(c).predecessors().map(|n| *n).collect();
+    assert_eq!(predecessors, vec!['A', 'C']);
+
+    graph.remove_node(c);
+    assert_eq!(graph.weak_ref(a).out_degree(), 0);
+    assert_eq!(graph.weak_ref(b).in_degree(), 0);
+    let d = graph.insert('D').weak();
+    assert_eq!(graph.weak_ref(d).in_degree(), 0);
+}
+
+#[test]
+pub fn test_predecessors_stay_consistent() {
+    let mut rng = Rng(0x7777_1234_abcd_0001);
+    let mut graph: Graph<usize, u32> = Graph::new();
+    let mut nodes: Vec<WeakNode<usize, u32>> = (0..10).map(|i| graph.insert(i).weak()).collect();
+    for step in 0..2000 {
+        let a = nodes[rng.next(nodes.len())];
+        let b = nodes[rng.next(nodes.len())];
+        match rng.next(10) {
+            0 => {
+                graph.remove_node(a);
+                nodes.retain(|&node| node != a);
+                nodes.push(graph.insert(step).weak());
+            }
+            1..=4 => {
+                graph.disconnect(a, b);
+            }
+            _ => graph.connect_weighted(a, b, 1),
+        }
+
+        for &node in &nodes {
+            let node = graph.weak_ref(node);
+            let mut expected: Vec<usize> = nodes
+                .iter()
+                .map(|&other| graph.weak_ref(other))
+                .filter(|other| other.neighbors().any(|n| n.weak() == node.weak()))
+                .map(|other| *other)
+                .collect();
+            let mut predecessors: Vec<usize> = node.predecessors().map(|n| *n).collect();
+            predecessors.sort_unstable();
+            expected.sort_unstable();
+            assert_eq!(predecessors, expected);
+            assert_eq!(node.in_degree(), expected.len());
+            assert_eq!(node.out_degree(), node.neighbors().count());
+        }
+    }
+}
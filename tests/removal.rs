@@ -0,0 +1,42 @@
+use graph::Graph;
+
+#[test]
+pub fn test_remove_node() {
+    let mut graph: Graph<char, u32> = Graph::new();
+    let a = graph.insert('A').weak();
+    let b = graph.insert('B').weak();
+    let c = graph.insert('C').weak();
+    graph.connect_undirected_weighted(a, b, 1);
+    graph.connect_undirected_weighted(b, c, 2);
+    graph.connect_weighted(a, c, 5);
+
+    assert_eq!(graph.remove_node(b), Some('B'));
+    assert_eq!(graph.remove_node(b), None);
+
+    // handles to the remaining nodes still resolve to the same nodes
+    assert_eq!(*graph.weak_ref(a), 'A');
+    assert_eq!(*graph.weak_ref(c), 'C');
+    let neighbors: Vec<char> = graph.weak_ref(a).neighbors().map(|n| *n).collect();
+    assert_eq!(neighbors, vec!['C']);
+    assert_eq!(graph.weak_ref(c).neighbors().count(), 0);
+
+    // the vacant slot is reused without disturbing the other nodes
+    let d = graph.insert('D').weak();
+    graph.connect_weighted(d, a, 4);
+    assert_eq!(*graph.weak_ref(a), 'A');
+    assert_eq!(*graph.weak_ref(d), 'D');
+    assert_eq!(graph.find(&'B').map(|n| *n), None);
+}
+
+#[test]
+pub fn test_disconnect() {
+    let mut graph: Graph<char, u32> = Graph::new();
+    let a = graph.insert('A').weak();
+    let b = graph.insert('B').weak();
+    graph.connect_undirected_weighted(a, b, 7);
+
+    assert_eq!(graph.disconnect(a, b), Some(7));
+    assert_eq!(graph.disconnect(a, b), None);
+    assert_eq!(graph.weak_ref(a).neighbors().count(), 0);
+    assert_eq!(graph.weak_ref(b).neighbors().count(), 1);
+}
@@ -0,0 +1,124 @@
+use graph::Graph;
+
+#[test]
+pub fn test_shortest_path_tree() {
+    let mut graph: Graph<char, u32> = Graph::new();
+    let a = graph.insert('A').weak();
+    let b = graph.insert('B').weak();
+    let c = graph.insert('C').weak();
+    let d = graph.insert('D').weak();
+    let unreachable = graph.insert('E').weak();
+    graph.connect_weighted(a, b, 4);
+    graph.connect_weighted(a, c, 1);
+    graph.connect_weighted(c, b, 2);
+    graph.connect_weighted(b, d, 5);
+    graph.connect_weighted(unreachable, a, 1);
+
+    let tree = graph.shortest_path_tree(graph.weak_ref(a));
+    assert_eq!(tree.distance_to(a), Some(&0));
+    assert_eq!(tree.distance_to(b), Some(&3));
+    assert_eq!(tree.distance_to(d), Some(&8));
+    assert_eq!(tree.distance_to(unreachable), None);
+    assert!(tree.path_to(unreachable).is_none());
+
+    let path = tree.path_to(d).unwrap();
+    assert_eq!(format!("{path:?}"), "['A', 'C', 'B', 'D']");
+    assert_eq!(path.len(), 8);
+
+    let order: Vec<(char, u32)> = tree.iter().map(|(node, dist)| (*node, *dist)).collect();
+    assert_eq!(order, vec![('A', 0), ('C', 1), ('B', 3), ('D', 8)]);
+}
+
+#[test]
+pub fn test_astar_on_grid() {
+    const SIZE: u32 = 30;
+    let mut graph: Graph<(u32, u32), u32> = Graph::new();
+    let cells: Vec<Vec<_>> = (0..SIZE)
+        .map(|x| (0..SIZE).map(|y| graph.insert((x, y)).weak()).collect())
+        .collect();
+    for x in 0..SIZE as usize {
+        for y in 0..SIZE as usize {
+            if x + 1 < SIZE as usize {
+                graph.connect_undirected_weighted(cells[x][y], cells[x + 1][y], 1);
+            }
+            if y + 1 < SIZE as usize {
+                // make some columns more expensive so the path is not trivial
+                let cost = if x % 4 == 1 { 3 } else { 1 };
+                graph.connect_undirected_weighted(cells[x][y], cells[x][y + 1], cost);
+            }
+        }
+    }
+    let start = graph.weak_ref(cells[0][0]);
+    let end = graph.weak_ref(cells[20][25]);
+    let (ex, ey) = *end;
+    let manhattan =
+        |node: graph::Node<'_, (u32, u32), u32>| node.0.abs_diff(ex) + node.1.abs_diff(ey);
+
+    let expected = graph.dijkstras(start, end).unwrap().len();
+    let search = graph.astar_search(start, end, manhattan);
+    assert_eq!(search.path().map(graph::Path::len), Some(expected));
+    assert_eq!(graph.astar(start, end, manhattan).unwrap().len(), expected);
+
+    let settled_by_dijkstra = graph
+        .shortest_path_tree(start)
+        .iter()
+        .take_while(|(node, _)| node.weak() != end.weak())
+        .count();
+    assert!(search.expanded() < settled_by_dijkstra);
+
+    // a heuristic of zero is just dijkstra's algorithm
+    let blind = graph.astar_search(start, end, |_| 0);
+    assert_eq!(blind.path().map(graph::Path::len), Some(expected));
+    assert!(search.expanded() < blind.expanded());
+}
+
+#[test]
+pub fn test_bellman_ford_with_rebates() {
+    let mut graph: Graph<char, i64> = Graph::new();
+    let a = graph.insert('A').weak();
+    let b = graph.insert('B').weak();
+    let c = graph.insert('C').weak();
+    let d = graph.insert('D').weak();
+    graph.connect_weighted(a, b, 4);
+    graph.connect_weighted(a, c, 2);
+    graph.connect_weighted(b, c, -3);
+    graph.connect_weighted(c, d, 2);
+
+    let paths = graph.bellman_ford(graph.weak_ref(a)).unwrap();
+    assert_eq!(paths.distance_to(c), Some(&1));
+    assert_eq!(paths.distance_to(d), Some(&3));
+    assert_eq!(paths.predecessor(c).map(|n| *n), Some('B'));
+    assert_eq!(
+        format!("{:?}", paths.path_to(d).unwrap()),
+        "['A', 'B', 'C', 'D']"
+    );
+    let order: Vec<char> = paths.iter().map(|(node, _)| *node).collect();
+    assert_eq!(order, vec!['A', 'C', 'D', 'B']);
+}
+
+#[test]
+pub fn test_bellman_ford_negative_cycle() {
+    let mut graph: Graph<char, i64> = Graph::new();
+    let a = graph.insert('A').weak();
+    let b = graph.insert('B').weak();
+    let c = graph.insert('C').weak();
+    let d = graph.insert('D').weak();
+    graph.connect_weighted(a, b, 1);
+    graph.connect_weighted(b, c, 2);
+    graph.connect_weighted(c, d, -4);
+    graph.connect_weighted(d, b, 1);
+
+    let cycle = graph.bellman_ford(graph.weak_ref(a)).err().unwrap();
+    let path = cycle.cycle();
+    assert_eq!(path.len(), -1);
+    let nodes: Vec<char> = path.iter().map(|n| *n).collect();
+    assert_eq!(nodes.len(), 4);
+    assert_eq!(nodes.first(), nodes.last());
+    assert!(!nodes.contains(&'A'));
+
+    // a cycle that can't be reached from the start doesn't matter
+    let e = graph.insert('E').weak();
+    graph.connect_weighted(e, a, 1);
+    graph.disconnect(a, b);
+    assert!(graph.bellman_ford(graph.weak_ref(e)).is_ok());
+}
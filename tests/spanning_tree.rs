@@ -0,0 +1,68 @@
+use graph::Graph;
+
+fn make_graph() -> Graph<char, u32> {
+    let mut graph = Graph::new();
+    let nodes: Vec<_> = "ABCDEFG".chars().map(|c| graph.insert(c).weak()).collect();
+    for (start, end, weight) in [
+        (0, 1, 7),
+        (0, 3, 5),
+        (1, 2, 8),
+        (1, 3, 9),
+        (1, 4, 7),
+        (2, 4, 5),
+        (3, 4, 15),
+        (3, 5, 6),
+        (4, 5, 8),
+        (4, 6, 9),
+        (5, 6, 11),
+    ] {
+        graph.connect_undirected_weighted(nodes[start], nodes[end], weight);
+    }
+    // a second component, connected in one direction only
+    let x = graph.insert('X').weak();
+    let y = graph.insert('Y').weak();
+    graph.connect_weighted(x, y, 2);
+    graph
+}
+
+/// The total weight of a forest, where the only path between adjacent nodes is their edge
+fn total_weight(tree: &Graph<char, u32>) -> u32 {
+    let mut total = 0;
+    for c in "ABCDEFGXY".chars() {
+        let node = tree.find(&c).unwrap();
+        for next in node.neighbors() {
+            total += tree.dijkstras(node, next).unwrap().len();
+        }
+    }
+    total / 2
+}
+
+#[test]
+pub fn test_minimum_spanning_tree() {
+    let graph = make_graph();
+    let kruskal = graph.minimum_spanning_tree();
+    let prim = graph.minimum_spanning_tree_prim();
+    for tree in [&kruskal, &prim] {
+        assert_eq!(total_weight(tree), 39 + 2);
+        assert!(!tree.connectivity().same_component(
+            tree.find(&'A').unwrap().weak(),
+            tree.find(&'X').unwrap().weak()
+        ));
+    }
+
+    // the forest has its own identity, but translates the nodes of the graph it spans
+    let a = graph.find(&'A').unwrap().weak();
+    let d = graph.find(&'D').unwrap().weak();
+    assert!(kruskal.try_weak_ref(a).is_err());
+    assert_eq!(
+        kruskal.weak_ref(kruskal.translate(a)).neighbors().count(),
+        2
+    );
+    let (a, d) = (prim.translate(a), prim.translate(d));
+    assert_eq!(
+        prim.dijkstras(prim.weak_ref(a), prim.weak_ref(d))
+            .unwrap()
+            .len(),
+        5
+    );
+}
@@ -0,0 +1,214 @@
+mod common;
+
+use common::Rng;
+use graph::{Graph, UnGraph, Undirected};
+
+#[test]
+pub fn test_single_weight() {
+    let mut graph: UnGraph<char, u32> = UnGraph::new();
+    let a = graph.insert('A').weak();
+    let b = graph.insert('B').weak();
+    graph.connect_weighted(a, b, 2);
+
+    assert_eq!(graph.edge_weight(a, b), Some(&2));
+    assert_eq!(graph.edge_weight(b, a), Some(&2));
+    *graph.edge_weight_mut(b, a).unwrap() += 10;
+    assert_eq!(graph.edge_weight(a, b), Some(&12));
+
+    // connecting from the other end replaces the weight rather than adding an edge
+    graph.connect_weighted(b, a, 5);
+    assert_eq!(graph.edge_weight(a, b), Some(&5));
+    assert_eq!(graph.edge_count(), 1);
+    let edges: Vec<(char, char, u32)> = graph
+        .edges()
+        .map(|edge| (*edge.source, *edge.target, *edge.weight))
+        .collect();
+    assert_eq!(edges, vec![('A', 'B', 5)]);
+}
+
+#[test]
+pub fn test_neighbors_and_degrees() {
+    let mut graph: UnGraph<char, u32> = UnGraph::new();
+    let a = graph.insert('A').weak();
+    let b = graph.insert('B').weak();
+    let c = graph.insert('C').weak();
+    graph.connect_weighted(a, b, 1);
+    graph.connect_weighted(c, a, 2);
+    graph.connect_weighted(a, a, 3);
+
+    let neighbors: Vec<char> = graph.weak_ref(a).neighbors().map(|n| *n).collect();
+    assert_eq!(neighbors, vec!['A', 'B', 'C']);
+    let predecessors: Vec<char> = graph.weak_ref(a).predecessors().map(|n| *n).collect();
+    assert_eq!(predecessors, neighbors);
+    let neighbors: Vec<char> = graph.weak_ref(c).neighbors().map(|n| *n).collect();
+    assert_eq!(neighbors, vec!['A']);
+    assert_eq!(graph.weak_ref(a).in_degree(), 3);
+    assert_eq!(graph.weak_ref(a).out_degree(), 3);
+    assert_eq!(graph.weak_ref(c).in_degree(), 1);
+
+    let edges: Vec<(char, char, u32)> = graph
+        .weak_ref(a)
+        .edges()
+        .map(|edge| (*edge.source, *edge.target, *edge.weight))
+        .collect();
+    assert_eq!(edges, vec![('A', 'A', 3), ('A', 'B', 1), ('A', 'C', 2)]);
+    assert_eq!(graph.edge_count(), 3);
+}
+
+#[test]
+pub fn test_searches() {
+    let mut graph: UnGraph<char, u32> = UnGraph::new();
+    let nodes: Vec<_> = "ABCDE".chars().map(|c| graph.insert(c).weak()).collect();
+    graph.connect_weighted(nodes[1], nodes[0], 4);
+    graph.connect_weighted(nodes[2], nodes[0], 1);
+    graph.connect_weighted(nodes[1], nodes[2], 1);
+    graph.connect_weighted(nodes[3], nodes[1], 5);
+
+    let path = graph
+        .dijkstras(graph.weak_ref(nodes[0]), graph.weak_ref(nodes[3]))
+        .unwrap();
+    assert_eq!(path.iter().map(|n| *n).collect::<String>(), "ACBD");
+    assert_eq!(path.len(), 7);
+    assert!(graph
+        .dijkstras(graph.weak_ref(nodes[0]), graph.weak_ref(nodes[4]))
+        .is_none());
+
+    let breadth: String = graph
+        .weak_ref(nodes[3])
+        .breadth_first()
+        .map(|n| *n)
+        .collect();
+    assert_eq!(breadth, "DBAC");
+    let depth: String = graph.weak_ref(nodes[3]).depth_first().map(|n| *n).collect();
+    assert_eq!(depth, "DBCA");
+}
+
+#[test]
+pub fn test_disconnect_either_end() {
+    let mut graph: UnGraph<char> = UnGraph::new();
+    let a = graph.insert('A').weak();
+    let b = graph.insert('B').weak();
+    let c = graph.insert('C').weak();
+    graph.connect(a, b);
+    graph.connect(b, c);
+
+    assert_eq!(graph.disconnect(b, a), Some(()));
+    assert_eq!(graph.disconnect(a, b), None);
+    assert_eq!(graph.disconnect(b, c), Some(()));
+    assert_eq!(graph.edge_count(), 0);
+    assert_eq!(graph.weak_ref(b).in_degree(), 0);
+    assert_eq!(graph.weak_ref(b).neighbors().count(), 0);
+}
+
+#[test]
+pub fn test_remove_node() {
+    let mut graph: UnGraph<char, u32> = UnGraph::new();
+    let a = graph.insert('A').weak();
+    let b = graph.insert('B').weak();
+    let c = graph.insert('C').weak();
+    graph.connect_weighted(a, b, 1);
+    graph.connect_weighted(c, b, 2);
+    graph.connect_weighted(a, c, 3);
+
+    assert_eq!(graph.remove_node(b), Some('B'));
+    assert_eq!(graph.edge_count(), 1);
+    assert_eq!(graph.weak_ref(a).out_degree(), 1);
+    assert_eq!(graph.weak_ref(c).out_degree(), 1);
+    let d = graph.insert('D').weak();
+    assert_eq!(graph.weak_ref(d).neighbors().count(), 0);
+    graph[d] = 'E';
+    assert_eq!(graph[d], 'E');
+}
+
+#[test]
+pub fn test_eulerian() {
+    let mut graph: UnGraph<char> = UnGraph::new();
+    let nodes: Vec<_> = "ABCD".chars().map(|c| graph.insert(c).weak()).collect();
+    for (start, end) in [(0, 1), (1, 2), (2, 0), (2, 3)] {
+        graph.connect(nodes[start], nodes[end]);
+    }
+
+    let path = graph.eulerian_path().unwrap();
+    assert_eq!(path.iter().map(|n| *n).collect::<String>(), "CABCD");
+    assert!(graph.eulerian_circuit(graph.weak_ref(nodes[0])).is_err());
+
+    let e = graph.insert('E').weak();
+    graph.connect(nodes[3], e);
+    graph.connect(e, nodes[2]);
+    let circuit = graph.eulerian_circuit(graph.weak_ref(nodes[0])).unwrap();
+    assert_eq!(circuit.iter().count(), 7);
+    assert_eq!(
+        circuit.iter().next().map(|n| *n),
+        circuit.iter().last().map(|n| *n)
+    );
+}
+
+#[test]
+pub fn test_matches_directed_pairs() {
+    let mut rng = Rng(0x2468_ace0_1357_9bdf);
+    for _ in 0..50 {
+        let mut undirected: UnGraph<usize, u32> = UnGraph::new();
+        let mut directed: Graph<usize, u32> = Graph::new();
+        let len = 2 + rng.next(8);
+        let nodes: Vec<_> = (0..len).map(|i| undirected.insert(i).weak()).collect();
+        let pairs: Vec<_> = (0..len).map(|i| directed.insert(i).weak()).collect();
+        for _ in 0..rng.next(20) {
+            let (start, end) = (rng.next(len), rng.next(len));
+            let weight = 1 + rng.next(9) as u32;
+            if undirected.edge_weight(nodes[start], nodes[end]).is_some() {
+                continue;
+            }
+            undirected.connect_weighted(nodes[start], nodes[end], weight);
+            directed.connect_undirected_weighted(pairs[start], pairs[end], weight);
+        }
+
+        for i in 0..len {
+            let mut expected: Vec<usize> = directed
+                .weak_ref(pairs[i])
+                .neighbors()
+                .map(|n| *n)
+                .collect();
+            let mut actual: Vec<usize> = undirected
+                .weak_ref(nodes[i])
+                .neighbors()
+                .map(|n| *n)
+                .collect();
+            expected.sort_unstable();
+            actual.sort_unstable();
+            assert_eq!(actual, expected);
+            for j in 0..len {
+                let expected = directed
+                    .dijkstras(directed.weak_ref(pairs[i]), directed.weak_ref(pairs[j]))
+                    .map(|path| path.len());
+                let actual = undirected
+                    .dijkstras(undirected.weak_ref(nodes[i]), undirected.weak_ref(nodes[j]))
+                    .map(|path| path.len());
+                assert_eq!(actual, expected);
+            }
+        }
+    }
+}
+
+#[test]
+pub fn test_clone_keeps_kind() {
+    let mut graph: UnGraph<char, u32> = UnGraph::new();
+    let a = graph.insert('A').weak();
+    let b = graph.insert('B').weak();
+    graph.connect_weighted(a, b, 2);
+
+    // a clone of the graph behind `Deref` is still undirected, and says so in its type
+    let mut inner: Graph<char, u32, Undirected> = (*graph).clone();
+    let (x, y) = (inner.translate(a), inner.translate(b));
+    inner.connect_weighted(y, x, 7);
+    assert_eq!(inner.edge_weight(x, y), Some(&7));
+    assert_eq!(inner.edge_count(), 1);
+
+    // while a directed graph keeps both directions apart
+    let mut directed: Graph<char, u32> = Graph::new();
+    let x = directed.insert('A').weak();
+    let y = directed.insert('B').weak();
+    directed.connect_weighted(x, y, 2);
+    directed.connect_weighted(y, x, 7);
+    assert_eq!(directed.edge_weight(x, y), Some(&2));
+    assert_eq!(directed.edge_count(), 2);
+}
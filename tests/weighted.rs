@@ -1,3 +1,11 @@
+mod common;
+
+use std::{
+    collections::BTreeMap,
+    time::{Duration, Instant},
+};
+
+use common::Rng;
 use graph::Graph;
 
 fn make_graph() -> Graph<char, u32> {
@@ -32,3 +40,103 @@ pub fn test_dijkstras() {
     let fourth = path_iter.next();
     assert!(fourth.is_none());
 }
+
+/// A random graph along with the weight of each of its edges by start and end, recorded as they are added
+fn random_graph(
+    rng: &mut Rng,
+    nodes: usize,
+    edges: usize,
+) -> (Graph<usize, u64>, Vec<BTreeMap<usize, u64>>) {
+    let mut graph = Graph::new();
+    let mut weights = vec![BTreeMap::new(); nodes];
+    let handles: Vec<_> = (0..nodes).map(|i| graph.insert(i).weak()).collect();
+    for _ in 0..edges {
+        let (start, end) = (rng.next(nodes), rng.next(nodes));
+        let weight = rng.next(100) as u64 + 1;
+        graph.connect_weighted(handles[start], handles[end], weight);
+        // connecting the same nodes again replaces the weight
+        weights[start].insert(end, weight);
+    }
+    (graph, weights)
+}
+
+/// Quadratic reference implementation over the recorded edge weights
+fn reference_distance(edges: &[BTreeMap<usize, u64>], start: usize, end: usize) -> Option<u64> {
+    let nodes = edges.len();
+    let mut distance = vec![None; nodes];
+    let mut done = vec![false; nodes];
+    distance[start] = Some(0);
+    while let Some(next) = (0..nodes)
+        .filter(|&i| !done[i] && distance[i].is_some())
+        .min_by_key(|&i| distance[i])
+    {
+        done[next] = true;
+        for (&step, &weight) in &edges[next] {
+            let new = distance[next].unwrap() + weight;
+            if distance[step].is_none_or(|old| new < old) {
+                distance[step] = Some(new);
+            }
+        }
+    }
+    distance[end]
+}
+
+#[test]
+pub fn test_dijkstras_matches_reference() {
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+    let (graph, weights) = random_graph(&mut rng, 300, 1500);
+    for _ in 0..10 {
+        let start = rng.next(300);
+        let end = rng.next(300);
+        let path = graph.dijkstras(graph.find(&start).unwrap(), graph.find(&end).unwrap());
+        assert_eq!(
+            path.map(|path| path.len()),
+            reference_distance(&weights, start, end)
+        );
+    }
+}
+
+#[test]
+pub fn test_dijkstras_long_path() {
+    // scanning every remaining node for the next one to settle would take billions of steps on this path
+    const NODES: usize = 100_000;
+    let mut graph: Graph<usize, u64> = Graph::new();
+    let nodes: Vec<_> = (0..NODES).map(|i| graph.insert(i).weak()).collect();
+    for pair in nodes.windows(2) {
+        graph.connect_weighted(pair[0], pair[1], 1);
+    }
+    let leaf = graph.insert(NODES).weak();
+    graph.connect_weighted(nodes[0], leaf, 2);
+
+    let start = graph.weak_ref(nodes[0]);
+    // both targets have no outgoing edges, so the search has to stop when they are settled rather than when one
+    // of their edges is followed
+    let path = graph
+        .dijkstras(start, graph.weak_ref(nodes[NODES - 1]))
+        .unwrap();
+    assert_eq!(path.len(), NODES as u64 - 1);
+    assert_eq!(path.iter().count(), NODES);
+    let path = graph.dijkstras(start, graph.weak_ref(leaf)).unwrap();
+    assert_eq!(path.iter().map(|n| *n).collect::<Vec<_>>(), vec![0, NODES]);
+    assert_eq!(path.len(), 2);
+}
+
+#[test]
+#[ignore = "timing test that depends on the speed of the machine; run with --ignored"]
+pub fn test_dijkstras_large_sparse() {
+    const NODES: usize = 200_000;
+    let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+    let (graph, _) = random_graph(&mut rng, NODES, NODES * 4);
+
+    let begin = Instant::now();
+    for _ in 0..5 {
+        let start = graph.find(&rng.next(NODES)).unwrap();
+        let end = graph.find(&rng.next(NODES)).unwrap();
+        if let Some(path) = graph.dijkstras(start, end) {
+            assert_eq!(*path.iter().next().unwrap(), *start);
+            assert_eq!(*path.iter().last().unwrap(), *end);
+        }
+    }
+    // a quadratic search would need billions of steps here
+    assert!(begin.elapsed() < Duration::from_secs(30));
+}